@@ -24,4 +24,11 @@ impl Action for Event {
     fn handle(&self) -> Option<&str> {
         None
     }
+
+    fn name(&self) -> Option<&str> {
+        match *self {
+            Self::Exit => Some("quit"),
+            Self::SetTheme(_) | Self::SetAccount(_) => None,
+        }
+    }
 }
@@ -1,16 +1,39 @@
 use crate::keybind::KeyBind;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use std::fmt::Write;
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
 
 const BIND_SEPARATOR: &str = ", ";
 const MODIFIER_SEPARATOR: &str = "+";
+const KEY_SEPARATOR: &str = " ";
+
+// How long a started sequence or count waits for the next key
+const PENDING_TIMEOUT: Duration = Duration::from_millis(1000);
+const MAX_COUNT: usize = 999;
 
 pub trait Action {
+    /// Label for the action in the keys footer.
     fn handle(&self) -> Option<&str>;
+
+    /// What the action is called in the keymap file, if it can be bound there.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether a count before the keys, like the 5 in `5s`, repeats the action.
+    fn repeatable(&self) -> bool {
+        false
+    }
 }
 
 pub struct Handler<T: Action + Clone> {
     pub inputs: Vec<KeyBind<T>>,
+    counts: bool,
+    pending: Vec<KeyEvent>,
+    count: Option<usize>,
+    last_key: Instant,
 }
 
 impl<T> Handler<T>
@@ -19,18 +42,100 @@ where
 {
     #[must_use]
     pub fn new(inputs: Vec<KeyBind<T>>) -> Self {
-        Self { inputs }
+        Self {
+            inputs,
+            counts: true,
+            pending: Vec::new(),
+            count: None,
+            last_key: Instant::now(),
+        }
     }
 
-    // TODO on app init, load binds from file?
-
+    /// For when digits are typed as text rather than read as counts.
     #[must_use]
-    pub fn handle(&self, key_event: KeyEvent) -> Option<T> {
-        if let Some(keybind) = self.inputs.iter().find(|&bind| bind.event == key_event) {
-            return Some(keybind.action.clone());
+    pub fn without_counts(inputs: Vec<KeyBind<T>>) -> Self {
+        Self {
+            counts: false,
+            ..Self::new(inputs)
         }
+    }
 
-        None
+    /// Actions for the key, once it completes a bind. A key that only starts one is held on to,
+    /// until the next key or until it has waited too long, see [`Self::flush`].
+    pub fn handle(&mut self, key_event: KeyEvent) -> Vec<T> {
+        let mut actions = self.flush();
+
+        actions.extend(self.press(key_event));
+
+        actions
+    }
+
+    /// Once the keys held on to have waited too long for a longer bind, fires the bind they make
+    /// up on their own, if any, and lets go of them. Meant to be called regularly.
+    pub fn flush(&mut self) -> Vec<T> {
+        if !self.is_expired() || (self.pending.is_empty() && self.count.is_none()) {
+            return Vec::new();
+        }
+
+        match self.find(&self.pending).map(|bind| bind.action.clone()) {
+            Some(action) => self.fire(action),
+            None => {
+                self.reset();
+
+                Vec::new()
+            }
+        }
+    }
+
+    fn press(&mut self, key_event: KeyEvent) -> Vec<T> {
+        self.last_key = Instant::now();
+
+        if let Some(digit) = self.count_digit(key_event) {
+            self.count = Some(
+                self.count
+                    .unwrap_or(0)
+                    .saturating_mul(10)
+                    .saturating_add(digit)
+                    .min(MAX_COUNT),
+            );
+
+            return Vec::new();
+        }
+
+        self.pending.push(key_event);
+
+        let exact = self.find(&self.pending).map(|bind| bind.action.clone());
+
+        // Wait to see whether a longer bind is being typed
+        if self.starts_longer(&self.pending) {
+            return Vec::new();
+        }
+
+        if let Some(action) = exact {
+            return self.fire(action);
+        }
+
+        // The keys before this one might have been a whole bind that was waited on
+        let previous = self.pending.len() - 1;
+        let waited = self
+            .find(&self.pending[..previous])
+            .map(|bind| bind.action.clone());
+
+        if let Some(action) = waited {
+            let mut actions = self.fire(action);
+            actions.extend(self.press(key_event));
+            return actions;
+        }
+
+        let started = previous > 0;
+        self.reset();
+
+        // Start over from this key, it might begin a bind of its own
+        if started {
+            return self.press(key_event);
+        }
+
+        Vec::new()
     }
 
     #[must_use]
@@ -40,10 +145,8 @@ where
         let mut binds: Vec<String> = Vec::new();
 
         for input in &self.inputs {
-            if input.event.modifiers.contains(KeyModifiers::SHIFT) {
-                if let KeyCode::Char(_) = input.event.code {
-                    continue;
-                }
+            if self.is_shifted_duplicate(input) {
+                continue;
             }
 
             if let Some(handle) = input.action.handle() {
@@ -55,24 +158,131 @@ where
 
                 let mut separator = BIND_SEPARATOR;
 
-                let index = result.map_or_else(
-                    || {
-                        actions.push(action.clone());
-                        binds.push(format!("{action}: "));
-                        separator = "";
-                        actions.len() - 1
-                    },
-                    |i| i,
-                );
+                let index = result.unwrap_or_else(|| {
+                    actions.push(action.clone());
+                    binds.push(format!("{action}: "));
+                    separator = "";
+                    actions.len() - 1
+                });
 
                 binds[index].push_str(
-                    format!("{}{}", separator, event_to_string(input.event).as_str()).as_str(),
+                    format!("{}{}", separator, keys_to_string(&input.keys).as_str()).as_str(),
                 );
             }
         }
 
+        if let Some(pending) = self.pending() {
+            binds.insert(0, format!("Pending: {pending}"));
+        }
+
         binds
     }
+
+    /// The count and keys typed so far towards a bind, if any.
+    #[must_use]
+    pub fn pending(&self) -> Option<String> {
+        if self.is_expired() || (self.pending.is_empty() && self.count.is_none()) {
+            return None;
+        }
+
+        let count = self
+            .count
+            .map(|count| count.to_string())
+            .unwrap_or_default();
+
+        Some(format!("{count}{}", keys_to_string(&self.pending)))
+    }
+
+    fn find(&self, keys: &[KeyEvent]) -> Option<&KeyBind<T>> {
+        if keys.is_empty() {
+            return None;
+        }
+
+        self.inputs.iter().find(|bind| bind.keys == keys)
+    }
+
+    fn starts_longer(&self, keys: &[KeyEvent]) -> bool {
+        self.inputs
+            .iter()
+            .any(|bind| bind.keys.len() > keys.len() && bind.keys.starts_with(keys))
+    }
+
+    // Digits count unless they are bound, or it's a 0 with no count before it
+    fn count_digit(&self, key_event: KeyEvent) -> Option<usize> {
+        if !self.counts || !self.pending.is_empty() || key_event.modifiers != KeyModifiers::NONE {
+            return None;
+        }
+
+        let digit = match key_event.code {
+            KeyCode::Char(c) => c.to_digit(10)?,
+            _ => return None,
+        };
+
+        if digit == 0 && self.count.is_none() {
+            return None;
+        }
+
+        if self
+            .inputs
+            .iter()
+            .any(|bind| bind.keys.first() == Some(&key_event))
+        {
+            return None;
+        }
+
+        usize::try_from(digit).ok()
+    }
+
+    fn fire(&mut self, action: T) -> Vec<T> {
+        let times = if action.repeatable() {
+            self.count.unwrap_or(1)
+        } else {
+            1
+        };
+
+        self.reset();
+
+        vec![action; times]
+    }
+
+    fn reset(&mut self) {
+        self.pending.clear();
+        self.count = None;
+    }
+
+    fn is_expired(&self) -> bool {
+        self.last_key.elapsed() > PENDING_TIMEOUT
+    }
+
+    // Shifted letters are bound alongside the plain ones so caps lock doesn't matter, there is no
+    // need to list both
+    fn is_shifted_duplicate(&self, input: &KeyBind<T>) -> bool {
+        let event = match input.keys.as_slice() {
+            [event] => event,
+            _ => return false,
+        };
+
+        let lower = match event.code {
+            KeyCode::Char(c) if event.modifiers.contains(KeyModifiers::SHIFT) => {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+            _ => return false,
+        };
+
+        self.inputs.iter().any(|other| {
+            other.keys.len() == 1
+                && other.keys[0].code == KeyCode::Char(lower)
+                && other.keys[0].modifiers == event.modifiers - KeyModifiers::SHIFT
+                && other.action.handle() == input.action.handle()
+        })
+    }
+}
+
+fn keys_to_string(keys: &[KeyEvent]) -> String {
+    keys.iter()
+        .map(|key| event_to_string(*key))
+        .collect::<Vec<String>>()
+        .join(KEY_SEPARATOR)
 }
 
 fn event_to_string(key_event: KeyEvent) -> String {
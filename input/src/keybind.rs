@@ -1,8 +1,27 @@
 use crate::handler::Action;
 use crossterm::event::KeyEvent;
 
+/// An action and the keys, pressed one after the other, that trigger it.
 #[derive(Clone)]
 pub struct KeyBind<T: Action + Clone> {
-    pub event: KeyEvent,
+    pub keys: Vec<KeyEvent>,
     pub action: T,
 }
+
+impl<T: Action + Clone> KeyBind<T> {
+    #[must_use]
+    pub fn new(event: KeyEvent, action: T) -> Self {
+        Self {
+            keys: vec![event],
+            action,
+        }
+    }
+
+    #[must_use]
+    pub fn sequence(keys: &[KeyEvent], action: T) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            action,
+        }
+    }
+}
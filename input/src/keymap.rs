@@ -0,0 +1,156 @@
+use crate::{handler::Action, keybind::KeyBind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+/// Action that takes a key away from whatever it does by default.
+pub const UNBIND: &str = "none";
+
+const MODIFIER_SEPARATOR: char = '+';
+
+/// Lays `overrides`, key strings mapped to action names, over `defaults`. A key given here replaces
+/// its default bind, and only actions `defaults` already has can be bound. Also returns why any
+/// override was skipped.
+pub fn rebind<T: Action + Clone>(
+    defaults: Vec<KeyBind<T>>,
+    overrides: &BTreeMap<String, String>,
+) -> (Vec<KeyBind<T>>, Vec<String>) {
+    let mut problems: Vec<String> = Vec::new();
+    let mut custom: Vec<(&str, Vec<KeyEvent>, Option<T>)> = Vec::new();
+
+    for (key, name) in overrides {
+        let keys = match parse_keys(key) {
+            Ok(keys) => keys,
+            Err(e) => {
+                problems.push(e);
+                continue;
+            }
+        };
+
+        if let Some((other, _, _)) = custom.iter().find(|(_, existing, _)| *existing == keys) {
+            problems.push(format!("`{key}` and `{other}` are the same keys"));
+            continue;
+        }
+
+        let action = if name == UNBIND {
+            None
+        } else {
+            match defaults
+                .iter()
+                .find(|bind| bind.action.name() == Some(name.as_str()))
+            {
+                Some(bind) => Some(bind.action.clone()),
+                None => {
+                    problems.push(format!("`{key}` is bound to unknown action `{name}`"));
+                    continue;
+                }
+            }
+        };
+
+        custom.push((key, keys, action));
+    }
+
+    let mut binds: Vec<KeyBind<T>> = defaults
+        .into_iter()
+        .filter(|bind| !custom.iter().any(|(_, keys, _)| *keys == bind.keys))
+        .collect();
+
+    binds.extend(
+        custom
+            .into_iter()
+            .filter_map(|(_, keys, action)| action.map(|action| KeyBind { keys, action })),
+    );
+
+    (binds, problems)
+}
+
+/// Reads keys pressed one after the other, separated by spaces, like `g g`.
+#[allow(clippy::missing_errors_doc)]
+pub fn parse_keys(keys: &str) -> Result<Vec<KeyEvent>, String> {
+    let keys = keys
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Result<Vec<KeyEvent>, String>>()?;
+
+    if keys.is_empty() {
+        return Err(String::from("An empty key can't be bound"));
+    }
+
+    Ok(keys)
+}
+
+/// Reads a key written like `q`, `ctrl+k`, `shift+tab` or `alt+F5`.
+#[allow(clippy::missing_errors_doc)]
+pub fn parse_key(key: &str) -> Result<KeyEvent, String> {
+    // The last part is the key itself, which can be `+`
+    let (modifiers, code) = match key.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => key.rsplit_once(MODIFIER_SEPARATOR).unwrap_or(("", key)),
+    };
+
+    let mut modifiers = modifiers
+        .split(MODIFIER_SEPARATOR)
+        .filter(|modifier| !modifier.is_empty())
+        .try_fold(KeyModifiers::NONE, |modifiers, modifier| {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => Ok(modifiers | KeyModifiers::CONTROL),
+                "alt" => Ok(modifiers | KeyModifiers::ALT),
+                "shift" => Ok(modifiers | KeyModifiers::SHIFT),
+                _ => Err(format!("`{key}` has unknown modifier `{modifier}`")),
+            }
+        })?;
+
+    let code = parse_code(code).ok_or_else(|| format!("`{key}` is not a key"))?;
+
+    // Terminals send shifted letters upper case with SHIFT set, and shift+tab as BackTab
+    let code = match code {
+        KeyCode::Char(c) if c.is_uppercase() => {
+            modifiers |= KeyModifiers::SHIFT;
+            code
+        }
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+        }
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        KeyCode::BackTab => {
+            modifiers |= KeyModifiers::SHIFT;
+            code
+        }
+        _ => code,
+    };
+
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+fn parse_code(code: &str) -> Option<KeyCode> {
+    let mut chars = code.chars();
+
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match code.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        other => {
+            let number = other.strip_prefix('f')?.parse::<u8>().ok()?;
+
+            KeyCode::F(number)
+        }
+    };
+
+    Some(code)
+}
@@ -1,2 +1,4 @@
 pub mod handler;
 pub mod keybind;
+pub mod keymap;
+pub mod mouse;
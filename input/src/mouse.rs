@@ -0,0 +1,61 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use std::time::{Duration, Instant};
+
+// Longest gap between two clicks on the same cell for them to count as a double click
+const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Press {
+    Click,
+    DoubleClick,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// A mouse event worth reacting to, and the cell it happened on.
+#[derive(Clone, Copy)]
+pub struct Pointer {
+    pub press: Press,
+    pub column: u16,
+    pub row: u16,
+}
+
+/// Turns raw mouse events into clicks, double clicks and scrolling.
+#[derive(Default)]
+pub struct MouseHandler {
+    last_click: Option<(u16, u16, Instant)>,
+}
+
+impl MouseHandler {
+    pub fn handle(&mut self, mouse_event: MouseEvent) -> Option<Pointer> {
+        let press = match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.click(mouse_event),
+            MouseEventKind::ScrollUp => Press::ScrollUp,
+            MouseEventKind::ScrollDown => Press::ScrollDown,
+            _ => return None,
+        };
+
+        Some(Pointer {
+            press,
+            column: mouse_event.column,
+            row: mouse_event.row,
+        })
+    }
+
+    fn click(&mut self, mouse_event: MouseEvent) -> Press {
+        let double = self.last_click.map_or(false, |(column, row, at)| {
+            column == mouse_event.column
+                && row == mouse_event.row
+                && at.elapsed() <= DOUBLE_CLICK_TIMEOUT
+        });
+
+        // A third click starts over rather than making another double click
+        if double {
+            self.last_click = None;
+            Press::DoubleClick
+        } else {
+            self.last_click = Some((mouse_event.column, mouse_event.row, Instant::now()));
+            Press::Click
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use crate::input_mapping::app_inputs;
 use app_event::event::Event;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use input::handler::Handler;
 use state::state_machine::StateMachine;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
 use tui::{backend::Backend, terminal::Frame};
 use twitch::account::Account;
-use ui::theme::Theme;
+use ui::{theme::Theme, toast::Severity};
 
 pub struct App {
     running: bool,
@@ -20,21 +21,32 @@ pub struct App {
 
 impl App {
     pub async fn new() -> Self {
-        let account: Option<Account> = (Account::load().await).ok();
+        let loaded = Account::load().await;
 
         let (sender, receiver) = mpsc::unbounded_channel();
 
         let (paste_sender, paste_receiver) = mpsc::unbounded_channel();
 
+        let mut state = StateMachine::new(loaded.is_ok(), sender, paste_receiver);
+
+        // Without an account the login is asked for, which is only news if there was one before
+        if let Err(e) = &loaded {
+            warn!("Could not load the account: {e:#}");
+
+            if Account::is_set_up() {
+                state.notify(Severity::Error, "Login Failed", &format!("{e:#}"));
+            }
+        }
+
         // TODO load inputs
 
         Self {
             running: true,
-            theme: Theme::default(),
-            state: StateMachine::new(account.is_some(), sender, paste_receiver),
+            theme: Theme::load(),
+            state,
             events: receiver,
-            input_handler: Handler::new(app_inputs()),
-            account,
+            input_handler: Handler::without_counts(app_inputs()),
+            account: loaded.ok(),
             paste_sender,
         }
     }
@@ -56,7 +68,7 @@ impl App {
     }
 
     pub fn handle_input(&mut self, key_event: KeyEvent) {
-        if let Some(event) = self.input_handler.handle(key_event) {
+        for event in self.input_handler.handle(key_event) {
             match event {
                 Event::Exit => self.running = false,
                 Event::SetTheme(_) | Event::SetAccount(_) => {}
@@ -66,6 +78,10 @@ impl App {
         self.state.handle(key_event);
     }
 
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent) {
+        self.state.mouse(mouse_event);
+    }
+
     pub fn handle_paste(&mut self, content: String) {
         if content.trim().is_empty() {
             return;
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: twitch_launcher [--config-dir <dir>] [<command>]
+
+Without a command the launcher starts as usual. Commands:
+  status [--list <name>] [--json]                Who is live in your favourites or a list
+  launch <handle> [--chat] [--profile <player>]  Open a channel in a player
+  lists                                          Names of your lists";
+
+pub enum Command {
+    Tui,
+    Status {
+        list: Option<String>,
+        json: bool,
+    },
+    Launch {
+        handle: String,
+        chat: bool,
+        profile: Option<String>,
+    },
+    Lists,
+}
+
+pub struct Args {
+    pub config_dir: Option<PathBuf>,
+    pub help: bool,
+    pub command: Command,
+}
+
+impl Args {
+    /// Reads the arguments the launcher was started with, skipping the program name.
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self {
+            config_dir: None,
+            help: false,
+            command: Command::Tui,
+        };
+
+        while let Some(arg) = args.next() {
+            match (arg.as_str(), &mut parsed.command) {
+                ("--config-dir", _) => {
+                    parsed.config_dir = Some(PathBuf::from(value(&arg, &mut args)?));
+                }
+                ("-h" | "--help", _) => parsed.help = true,
+                ("status", Command::Tui) => {
+                    parsed.command = Command::Status {
+                        list: None,
+                        json: false,
+                    };
+                }
+                ("launch", Command::Tui) => {
+                    let handle = args
+                        .next()
+                        .ok_or_else(|| format!("launch needs a channel handle\n{USAGE}"))?;
+
+                    parsed.command = Command::Launch {
+                        handle,
+                        chat: false,
+                        profile: None,
+                    };
+                }
+                ("lists", Command::Tui) => parsed.command = Command::Lists,
+                ("--list", Command::Status { list, .. }) => *list = Some(value(&arg, &mut args)?),
+                ("--json", Command::Status { json, .. }) => *json = true,
+                ("--chat", Command::Launch { chat, .. }) => *chat = true,
+                ("--profile", Command::Launch { profile, .. }) => {
+                    *profile = Some(value(&arg, &mut args)?);
+                }
+                _ => match arg.strip_prefix("--config-dir=") {
+                    Some(dir) => parsed.config_dir = Some(PathBuf::from(dir)),
+                    None => return Err(format!("Unknown argument {arg}\n{USAGE}")),
+                },
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn value(option: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("{option} needs a value\n{USAGE}"))
+}
@@ -0,0 +1,153 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::sync::mpsc::unbounded_channel;
+use twitch::{
+    account::Account,
+    channel::{status::Status, Channel, List},
+    player::Players,
+    status_cache::StatusCache,
+};
+
+// What `status --json` prints for each channel
+#[derive(Serialize)]
+struct ChannelStatus<'a> {
+    name: &'a str,
+    handle: &'a str,
+    status: &'a str,
+    game: Option<&'a str>,
+    title: Option<&'a str>,
+    viewers: Option<usize>,
+}
+
+/// Prints whether each channel in the favourites, or the named list, is live. Fails, after
+/// printing, if any of them couldn't be checked.
+pub async fn status(list: Option<&str>, json: bool) -> Result<()> {
+    let account = Account::load()
+        .await
+        .with_context(|| "No usable account, start the launcher to log in")?;
+
+    let mut channels = match list {
+        Some(name) => find_list(name)?.channels,
+        None => Channel::load_from_file(&Channel::favourites_file())
+            .with_context(|| "Could not load favourites")?,
+    };
+
+    let (tx, mut rx) = unbounded_channel();
+
+    // Scripts want to know what is live now, not what was earlier
+    Channel::check(&channels, &account, &StatusCache::new(Duration::ZERO), &tx);
+
+    // The check holds its own sender, so this ends once every status is in
+    drop(tx);
+
+    while let Some((handle, (status, stream))) = rx.recv().await {
+        for channel in &mut channels {
+            if channel.handle.eq_ignore_ascii_case(&handle) {
+                channel.status = status.clone();
+                channel.stream = stream.clone();
+            }
+        }
+    }
+
+    if json {
+        let statuses: Vec<ChannelStatus> = channels
+            .iter()
+            .map(|channel| ChannelStatus {
+                name: &channel.friendly_name,
+                handle: &channel.handle,
+                status: channel.status.message().trim(),
+                game: channel.stream.as_ref().map(|stream| stream.game.as_str()),
+                title: channel.stream.as_ref().map(|stream| stream.title.as_str()),
+                viewers: channel.stream.as_ref().and_then(|stream| stream.viewers),
+            })
+            .collect();
+
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+    } else {
+        for channel in &channels {
+            match &channel.stream {
+                Some(stream) => println!(
+                    "{}\t{}\t{}\t{}",
+                    channel.handle,
+                    channel.status.message().trim(),
+                    stream.game,
+                    stream.title
+                ),
+                None => println!("{}\t{}", channel.handle, channel.status.message().trim()),
+            }
+        }
+    }
+
+    // Like a token that didn't validate, which leaves every channel unknown
+    if channels
+        .iter()
+        .any(|channel| channel.status == Status::Unknown)
+    {
+        bail!("Could not check every channel, see the log for why");
+    }
+
+    Ok(())
+}
+
+/// Opens a channel the same way the launcher would, using what is saved for it if it is a
+/// favourite or in a list.
+pub fn launch(handle: &str, chat: bool, profile: Option<&str>) -> Result<()> {
+    let lists = List::load_all();
+    let favourites = Channel::load_from_file(&Channel::favourites_file()).unwrap_or_default();
+
+    let saved = favourites
+        .iter()
+        .map(|channel| (channel, None))
+        .chain(lists.iter().flat_map(|list| {
+            list.channels
+                .iter()
+                .map(move |channel| (channel, list.player.as_deref()))
+        }))
+        .find(|(channel, _)| channel.handle.eq_ignore_ascii_case(handle));
+
+    let (channel, preferred) = match saved {
+        Some((channel, list_player)) => (
+            channel.clone(),
+            channel.player.as_deref().or(list_player).map(str::to_owned),
+        ),
+        None => (Channel::new(handle.to_owned(), handle.to_owned()), None),
+    };
+
+    let players = Players::load();
+
+    let player = match profile {
+        Some(name) => players
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("No player profile named {name}"))?,
+        None => players
+            .choices(preferred.as_deref())
+            .into_iter()
+            .next()
+            .map(|(player, _)| player)
+            .ok_or_else(|| anyhow!("No player profiles set up"))?,
+    };
+
+    channel.launch(&player)?;
+
+    if chat {
+        channel.launch_chat()?;
+    }
+
+    Ok(())
+}
+
+/// Prints the name of every list, one per line.
+pub fn lists() {
+    for list in List::load_all() {
+        println!("{}", list.name);
+    }
+}
+
+fn find_list(name: &str) -> Result<List> {
+    List::load_all()
+        .into_iter()
+        .find(|list| list.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| anyhow!("No list named {name}"))
+}
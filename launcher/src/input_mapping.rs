@@ -1,19 +1,29 @@
 use app_event::event::Event;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use input::keybind::KeyBind;
+use state::keymap::{Keymap, APP};
 
 pub fn app_inputs() -> Vec<KeyBind<Event>> {
+    let defaults = default_app_inputs();
+
+    match Keymap::installed() {
+        Some(keymap) => keymap.binds(APP, defaults),
+        None => defaults,
+    }
+}
+
+pub fn default_app_inputs() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
-            action: Event::Exit,
-        },
-        KeyBind {
-            event: KeyEvent::new(
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Event::Exit,
+        ),
+        KeyBind::new(
+            KeyEvent::new(
                 KeyCode::Char('C'),
                 KeyModifiers::CONTROL | KeyModifiers::SHIFT,
             ),
-            action: Event::Exit,
-        },
+            Event::Exit,
+        ),
     ]
 }
@@ -0,0 +1,45 @@
+use anyhow::{anyhow, Context, Result};
+use state::settings::Settings;
+use std::{env, fs::create_dir_all};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::EnvFilter;
+use twitch::paths;
+
+const LOGS_DIR: &str = "logs";
+const LOG_FILE_PREFIX: &str = "twitch_launcher";
+const LOG_FILE_SUFFIX: &str = "log";
+// A file is started each day, only the most recent are kept
+const MAX_LOG_FILES: usize = 7;
+
+/// Takes precedence over `log` in the settings, with the same syntax.
+const LOG_ENV: &str = "TWITCH_LAUNCHER_LOG";
+
+/// Sends everything logged from here on to a file in the data directory. Anything written to the
+/// terminal would end up in the middle of the TUI.
+pub fn init() -> Result<()> {
+    let directives = env::var(LOG_ENV).unwrap_or_else(|_| Settings::load().log);
+
+    let filter = EnvFilter::try_new(&directives)
+        .map_err(|e| anyhow!("Could not use log levels {directives}: {e}"))?;
+
+    let dir = paths::data_file(LOGS_DIR);
+
+    create_dir_all(&dir).with_context(|| format!("Could not create {}", dir.display()))?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .with_context(|| "Could not open the log file")?;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(appender)
+        .with_ansi(false)
+        .try_init()
+        .map_err(|e| anyhow!("Could not start logging: {e}"))?;
+
+    Ok(())
+}
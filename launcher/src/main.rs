@@ -1,21 +1,81 @@
 #![crate_type = "bin"]
 
 mod app;
+mod args;
+mod cli;
 mod input_mapping;
+mod logging;
 mod terminal;
 
 use crate::{
     app::App,
+    args::{Args, Command, USAGE},
+    input_mapping::default_app_inputs,
     terminal::{display::Tui, event::Event},
 };
-use std::io;
+use crossterm::tty::IsTty;
+use state::keymap;
+use std::{env, io, process::exit};
+use tracing::error;
 use tui::backend::CrosstermBackend;
+use twitch::{paths, storage};
 
 // TODO allow setting?
 const TICK_INTERVAL: u64 = 250;
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
+    let command = match Args::parse(env::args().skip(1)) {
+        Ok(args) if args.help => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        Ok(args) => {
+            if let Some(dir) = args.config_dir {
+                paths::set_config_dir(&dir);
+            }
+
+            args.command
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            exit(2);
+        }
+    };
+
+    // Not being able to log shouldn't stop the launcher from working
+    if let Err(e) = logging::init() {
+        eprintln!("{e:#}");
+    }
+
+    storage::migrate_current_dir();
+
+    let result = match command {
+        Command::Tui => return run_tui().await,
+        Command::Status { list, json } => cli::status(list.as_deref(), json).await,
+        Command::Launch {
+            handle,
+            chat,
+            profile,
+        } => cli::launch(&handle, chat, profile.as_deref()),
+        Command::Lists => {
+            cli::lists();
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        error!("{e:#}");
+        eprintln!("{e:#}");
+        exit(1);
+    }
+
+    Ok(())
+}
+
+async fn run_tui() -> Result<(), std::io::Error> {
+    check_keymap()?;
+
     let mut app = App::new().await;
 
     let backend = CrosstermBackend::new(io::stderr());
@@ -33,10 +93,34 @@ async fn main() -> Result<(), std::io::Error> {
             Some(Event::Tick) => app.tick().await,
             Some(Event::Key(key_event)) => app.handle_input(key_event),
             Some(Event::Paste(content)) => app.handle_paste(content),
-            Some(Event::Mouse(_) | Event::Resize(_, _)) | None => {}
+            Some(Event::Mouse(mouse_event)) => app.handle_mouse(mouse_event),
+            Some(Event::Resize(_, _)) => {}
+            // The input task is gone, likely after a panic, so nothing can be done any more
+            None => break,
         }
     }
 
     tui.exit()?;
     Ok(())
 }
+
+// Problems are shown before the terminal is taken over, or they'd never be seen. This is also
+// where the keymap is read and installed for the rest of the run
+fn check_keymap() -> Result<(), std::io::Error> {
+    let problems = keymap::install_checked(default_app_inputs());
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    for problem in &problems {
+        eprintln!("keymap: {problem}");
+    }
+
+    if io::stdin().is_tty() {
+        eprintln!("Those keys keep their defaults. Press Enter to continue");
+        io::stdin().read_line(&mut String::new())?;
+    }
+
+    Ok(())
+}
@@ -1,11 +1,28 @@
 use crate::app::App;
 use crate::terminal::event::Handler;
 use crossterm::{
+    cursor::Show,
     event::{DisableMouseCapture, EnableMouseCapture},
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::io;
+use std::{
+    fs::{create_dir_all, OpenOptions},
+    io::{self, Write},
+    panic,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::error;
 use tui::{backend::Backend, Terminal};
+use twitch::paths;
+
+const CRASH_LOG_FILE: &str = "crash.log";
+
+// Whether raw mode and the alternate screen are on, shared with the panic hook so the terminal is
+// only given back once
+static TAKEN_OVER: AtomicBool = AtomicBool::new(false);
 
 pub struct Tui<B: Backend> {
     terminal: Terminal<B>,
@@ -21,7 +38,11 @@ impl<B: Backend> Tui<B> {
     }
 
     pub fn init(&mut self) -> Result<(), std::io::Error> {
+        restore_on_panic();
+
         terminal::enable_raw_mode()?;
+        TAKEN_OVER.store(true, Ordering::SeqCst);
+
         crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
         self.terminal.hide_cursor()?;
         self.terminal.clear()?;
@@ -34,9 +55,81 @@ impl<B: Backend> Tui<B> {
     }
 
     pub fn exit(&mut self) -> Result<(), std::io::Error> {
-        terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
-        self.terminal.show_cursor()?;
-        Ok(())
+        restore()
+    }
+}
+
+// Covers returning early with an error, which skips `exit`
+impl<B: Backend> Drop for Tui<B> {
+    fn drop(&mut self) {
+        let _result = restore();
+    }
+}
+
+// Every step is tried even if one before it fails, so as much of the terminal as can be is given
+// back, and the first failure is the one reported
+fn restore() -> Result<(), std::io::Error> {
+    if !TAKEN_OVER.swap(false, Ordering::SeqCst) {
+        return Ok(());
     }
+
+    let raw_mode = terminal::disable_raw_mode();
+    let screen = crossterm::execute!(io::stdout(), LeaveAlternateScreen);
+    let mouse = crossterm::execute!(io::stdout(), DisableMouseCapture);
+    let cursor = crossterm::execute!(io::stdout(), Show);
+
+    raw_mode.and(screen).and(mouse).and(cursor)
+}
+
+// Gives the terminal back before the panic is printed, so the report can be read, and keeps a
+// copy in the crash log. Only a panic on the thread running the TUI ends it, one in a spawned
+// task is caught by tokio and the launcher keeps going, so that is only logged
+fn restore_on_panic() {
+    let default_hook = panic::take_hook();
+    let tui_thread = thread::current().id();
+
+    panic::set_hook(Box::new(move |info| {
+        let report = info.to_string();
+
+        error!("Panicked: {report}");
+
+        let logged = write_crash_log(&report);
+
+        if thread::current().id() != tui_thread {
+            return;
+        }
+
+        let _result = restore();
+
+        default_hook(info);
+
+        match logged {
+            Ok(file) => eprintln!("The crash was written to {}", file.display()),
+            Err(e) => eprintln!("Could not write the crash log: {e}"),
+        }
+    }));
+}
+
+fn write_crash_log(report: &str) -> Result<PathBuf, std::io::Error> {
+    let file = paths::data_file(CRASH_LOG_FILE);
+
+    if let Some(dir) = file.parent() {
+        create_dir_all(dir)?;
+    }
+
+    let mut log = OpenOptions::new().create(true).append(true).open(&file)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+
+    writeln!(
+        log,
+        "[{time}] twitch_launcher {} panicked in thread {}: {report}",
+        env!("CARGO_PKG_VERSION"),
+        thread::current().name().unwrap_or("<unnamed>"),
+    )?;
+
+    Ok(file)
 }
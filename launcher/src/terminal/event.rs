@@ -11,6 +11,7 @@ pub enum Event {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Paste(String),
+    #[allow(dead_code)]
     Resize(u16, u16),
 }
 
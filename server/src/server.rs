@@ -5,6 +5,7 @@ use std::{
 };
 
 use anyhow::{Context, Error, Result};
+use tracing::{debug, warn};
 use url::Url;
 
 #[must_use]
@@ -34,6 +35,8 @@ impl Server {
 
     #[allow(clippy::missing_errors_doc)]
     pub fn get_callback_data(&self) -> Result<(String, String)> {
+        debug!("Waiting for the Twitch redirect on port {}", self.port);
+
         if let Some(mut stream) = self.listener.incoming().flatten().next() {
             let buf_reader = BufReader::new(&mut stream);
             let http_request: Vec<_> = buf_reader
@@ -42,7 +45,7 @@ impl Server {
                 .take_while(|line| !line.is_empty())
                 .collect();
 
-            if let Some(req) = http_request.get(0) {
+            if let Some(req) = http_request.first() {
                 respond_success(stream)?;
 
                 let query = req
@@ -53,9 +56,11 @@ impl Server {
                 let pairs = get_params(query)?;
 
                 if let Some(error_description) = pairs.get("error_description") {
+                    warn!("Twitch refused the login: {error_description}");
+
                     return Err(Error::new(std::io::Error::new(
                         ErrorKind::Other,
-                        format!("Got error from Twitch - {error_description}",),
+                        format!("Got error from Twitch - {error_description}"),
                     )));
                 }
 
@@ -71,6 +76,8 @@ impl Server {
                 )));
             }
 
+            warn!("Got a request without a request line on the redirect port");
+
             respond_failure(stream)?;
 
             return Err(Error::new(std::io::Error::new(
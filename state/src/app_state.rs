@@ -1,5 +1,45 @@
+pub mod browse;
 pub mod exit;
+pub mod follows;
 pub mod home;
 pub mod lists;
 pub mod popup;
 pub mod startup;
+
+use tokio::sync::mpsc::UnboundedSender;
+use twitch::{channel::Channel, status_cache::StatusCache};
+
+use crate::{event::Event, state::AppState, transition::Transition};
+
+// Tabs in the order they're drawn
+pub const HOME_TAB: usize = 0;
+pub const LISTS_TAB: usize = 1;
+pub const FOLLOWS_TAB: usize = 2;
+pub const BROWSE_TAB: usize = 3;
+
+/// Switches to the tab that was clicked, unless it is the `current` one.
+pub fn open_tab(
+    tab: usize,
+    current: usize,
+    statuses: &StatusCache,
+    tx: &UnboundedSender<Event>,
+) -> Option<Transition> {
+    if tab == current {
+        return None;
+    }
+
+    let state = match tab {
+        HOME_TAB => {
+            let channels =
+                Channel::load_from_file(&Channel::favourites_file()).unwrap_or_else(|_| Vec::new());
+
+            AppState::Home(home::Home::init(channels.as_slice(), statuses, tx))
+        }
+        LISTS_TAB => AppState::Lists(lists::Lists::init(statuses, tx)),
+        FOLLOWS_TAB => AppState::Follows(follows::Follows::init(tx)),
+        BROWSE_TAB => AppState::Browse(browse::Browse::init(tx)),
+        _ => return None,
+    };
+
+    Some(Transition::To(state))
+}
@@ -0,0 +1,417 @@
+use std::mem::swap;
+
+use async_trait::async_trait;
+use crossterm::event::{KeyEvent, MouseEvent};
+use input::{handler::Handler, mouse::MouseHandler};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tui::{backend::Backend, terminal::Frame};
+use twitch::{
+    account::Account,
+    browse::{self, BrowseUpdate, Category},
+    channel::Channel,
+    player::Players,
+    status_cache::StatusCache,
+    status_service::SharedService,
+};
+use ui::{
+    column::Column,
+    hit::Hits,
+    panel::{Browse as BrowsePanel, Panel},
+    render::{self, BrowseList},
+    theme::Theme,
+};
+
+use crate::{
+    app_state::{
+        exit::Exit,
+        follows::Follows,
+        home::Home,
+        open_tab,
+        popup::{player_popup, theme_popup, Popup},
+        BROWSE_TAB,
+    },
+    event::Event,
+    input_mappings::browse_inputs,
+    launch::{self, PendingLaunch},
+    mouse::{gesture, Gesture},
+    settings::Settings,
+    state::{AppState, MoveDirection, MoveEnd, State},
+    transition::Transition,
+    util::{index_add, index_subtract},
+};
+
+// Categories or the streams of the open one, paged in as the highlight reaches the end
+#[derive(Clone)]
+struct Paged<T> {
+    items: Vec<T>,
+    highlight: usize,
+    cursor: Option<String>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl<T: Clone> Paged<T> {
+    const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            highlight: 0,
+            cursor: None,
+            loading: false,
+            error: None,
+        }
+    }
+
+    fn received(&mut self, page: Result<browse::Page<T>, String>) {
+        self.loading = false;
+
+        match page {
+            Ok(page) => {
+                self.items.extend(page.items);
+                self.cursor = page.cursor;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // Whether moving down from here should fetch the next page instead of wrapping around
+    fn wants_more(&self) -> bool {
+        self.cursor.is_some() && self.highlight + 1 >= self.items.len()
+    }
+
+    fn cycle(&mut self, direction: &MoveDirection) {
+        self.highlight = match direction {
+            MoveDirection::Down => index_add(self.highlight, self.items.len()),
+            MoveDirection::Up => index_subtract(self.highlight, self.items.len()),
+            _ => self.highlight,
+        };
+    }
+
+    fn jump(&mut self, end: &MoveEnd) {
+        self.highlight = match end {
+            MoveEnd::First => 0,
+            MoveEnd::Last => self.items.len().saturating_sub(1),
+        };
+    }
+
+    fn as_list(&self) -> BrowseList<'_, T> {
+        BrowseList {
+            items: &self.items,
+            highlight: self.highlight,
+            loading: self.loading,
+            error: self.error.as_deref(),
+        }
+    }
+}
+
+pub struct Browse {
+    categories: Paged<Category>,
+    open_category: Option<Category>,
+    streams: Paged<Channel>,
+    focused_panel: BrowsePanel,
+    browse_check: UnboundedReceiver<BrowseUpdate>,
+    browse_check_sender: UnboundedSender<BrowseUpdate>,
+    service: SharedService,
+    input_handler: Handler<Event>,
+    players: Players,
+    pending_launch: Option<PendingLaunch>,
+    columns: Vec<Column>,
+    hits: Hits<BrowsePanel>,
+    mouse: MouseHandler,
+}
+
+impl Browse {
+    pub fn new(players: &Players, columns: &[Column]) -> Self {
+        let (sender, receiver) = unbounded_channel();
+
+        Self {
+            categories: Paged::new(),
+            open_category: None,
+            streams: Paged::new(),
+            focused_panel: BrowsePanel::default(),
+            browse_check: receiver,
+            browse_check_sender: sender,
+            service: SharedService::default(),
+            input_handler: Handler::new(browse_inputs()),
+            players: players.clone(),
+            pending_launch: None,
+            columns: columns.to_vec(),
+            hits: Hits::default(),
+            mouse: MouseHandler::default(),
+        }
+    }
+
+    fn highlight(&mut self, panel: BrowsePanel, index: usize) {
+        self.focused_panel = panel;
+
+        match panel {
+            BrowsePanel::Categories => self.categories.highlight = index,
+            BrowsePanel::Streams => self.streams.highlight = index,
+        }
+    }
+
+    pub fn from_existing(state: &mut Self) -> Self {
+        state.browse_check();
+
+        let mut browse = Self::new(&state.players, &state.columns);
+
+        browse.categories = state.categories.clone();
+        browse.open_category = state.open_category.clone();
+        browse.streams = state.streams.clone();
+        browse.focused_panel = state.focused_panel;
+        browse.pending_launch = state.pending_launch.take();
+        browse.service = state.service.clone();
+
+        // Pages still in flight were sent to the old receiver
+        swap(&mut browse.browse_check, &mut state.browse_check);
+        swap(
+            &mut browse.browse_check_sender,
+            &mut state.browse_check_sender,
+        );
+
+        browse
+    }
+
+    pub fn init(tx: &UnboundedSender<Event>) -> Self {
+        let _result = tx.send(Event::LoadCategories);
+
+        // Loading is shown once the categories are actually being fetched
+        Self::new(&Players::load(), &Settings::load().columns)
+    }
+
+    pub fn browse_check(&mut self) {
+        while let Ok(update) = self.browse_check.try_recv() {
+            match update {
+                BrowseUpdate::Categories(page) => self.categories.received(page),
+                BrowseUpdate::Streams(category_id, page) => {
+                    // Another category was opened since this was asked for
+                    if self.open_category.as_ref().map(|category| &category.id)
+                        != Some(&category_id)
+                    {
+                        continue;
+                    }
+
+                    self.streams.received(page);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl State for Browse {
+    async fn tick(&self, _: &Option<Account>, _: u64, _: UnboundedSender<Event>) {}
+
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+        self.hits = render::browse(
+            theme,
+            frame,
+            &self.categories.as_list(),
+            self.open_category.as_ref(),
+            &self.streams.as_list(),
+            &self.columns,
+            self.focused_panel,
+            &self.input_handler.render(),
+            timer,
+        );
+    }
+
+    fn transition(
+        &mut self,
+        event: Event,
+        account: &Option<Account>,
+        statuses: &StatusCache,
+        tx: UnboundedSender<Event>,
+    ) -> Option<Transition> {
+        match event {
+            Event::Exited => Some(Transition::To(AppState::Exit(Exit::new()))),
+            Event::LoadCategories => {
+                match account {
+                    Some(acc) => {
+                        self.categories.loading = true;
+
+                        browse::fetch_categories(
+                            acc,
+                            &self.service,
+                            statuses.rate_limit(),
+                            self.categories.cursor.clone(),
+                            &self.browse_check_sender,
+                        );
+                    }
+                    None => {
+                        self.categories.error = Some(String::from("No account to browse with"));
+                    }
+                }
+
+                None
+            }
+            Event::LoadStreams => {
+                if let (Some(acc), Some(category)) = (account, &self.open_category) {
+                    self.streams.loading = true;
+
+                    browse::fetch_streams(
+                        acc,
+                        &self.service,
+                        statuses,
+                        &category.id,
+                        self.streams.cursor.clone(),
+                        &self.browse_check_sender,
+                    );
+                }
+
+                None
+            }
+            Event::ChoicePopupStarted((title, message, options, callback)) => {
+                Some(Transition::Push(AppState::Popup(Popup::new_choice(
+                    title, message, &options, callback,
+                ))))
+            }
+            Event::InputPopupStarted((title, message, callback)) => Some(Transition::Push(
+                AppState::Popup(Popup::new_input(title, message, callback)),
+            )),
+            Event::TimedInfoPopupStarted((title, message, duration, callback)) => {
+                Some(Transition::Push(AppState::Popup(Popup::new_timed_info(
+                    title, message, duration, callback,
+                ))))
+            }
+            Event::PlayerChoice(choice) => {
+                if let Some(channel) = self.streams.items.get(self.streams.highlight) {
+                    self.pending_launch =
+                        launch::player_chosen(channel, None, &self.players, choice, &tx);
+                }
+
+                None
+            }
+            Event::QualitiesFetched(id, qualities) => {
+                launch::qualities_fetched(&mut self.pending_launch, id, qualities, &tx);
+
+                None
+            }
+            Event::QualityChoice(choice) => {
+                // Browsed channels aren't saved anywhere, so the choice isn't remembered
+                let _channel = launch::quality_chosen(&mut self.pending_launch, choice, &tx);
+
+                None
+            }
+            Event::ChannelSelected(channel, player, chat) => {
+                launch::open(&channel, &player, chat, &tx);
+
+                None
+            }
+            Event::SelectTab(tab) => open_tab(tab, BROWSE_TAB, statuses, &tx),
+            Event::CycleTab(MoveDirection::Left) => {
+                Some(Transition::To(AppState::Follows(Follows::init(&tx))))
+            }
+            Event::CycleTab(MoveDirection::Right) => {
+                // TODO shouldn't be reloading this every time
+                let channels = Channel::load_from_file(&Channel::favourites_file())
+                    .unwrap_or_else(|_| Vec::new());
+
+                Some(Transition::To(AppState::Home(Home::init(
+                    channels.as_slice(),
+                    statuses,
+                    &tx,
+                ))))
+            }
+            _ => None,
+        }
+    }
+
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
+        self.input_handler.handle(key_event)
+    }
+
+    fn flush(&mut self) -> Vec<Event> {
+        self.input_handler.flush()
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        match gesture(&mut self.mouse, &self.hits, mouse_event) {
+            Some(Gesture::Tab(tab)) => vec![Event::SelectTab(tab)],
+            Some(Gesture::Focus(panel)) => {
+                self.focused_panel = panel;
+
+                Vec::new()
+            }
+            Some(Gesture::Highlight(panel, index)) => {
+                self.highlight(panel, index);
+
+                Vec::new()
+            }
+            Some(Gesture::Open(panel, index)) => {
+                self.highlight(panel, index);
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Scroll(panel, direction)) => {
+                self.focused_panel = panel;
+
+                vec![Event::CycleHighlight(direction)]
+            }
+            Some(Gesture::Choose(_)) | None => Vec::new(),
+        }
+    }
+
+    fn process(&mut self, action: Event, tx: &UnboundedSender<Event>) {
+        match action {
+            Event::Exited | Event::CycleTab(_) | Event::SelectTab(_) | Event::ShowNotifications => {
+                let _result = tx.send(action);
+            }
+            Event::PickTheme => theme_popup(tx),
+            Event::CycleHighlight(direction) => match self.focused_panel {
+                BrowsePanel::Categories => {
+                    if matches!(direction, MoveDirection::Down) && self.categories.wants_more() {
+                        if !self.categories.loading {
+                            self.categories.loading = true;
+                            let _result = tx.send(Event::LoadCategories);
+                        }
+                    } else {
+                        self.categories.cycle(&direction);
+                    }
+                }
+                BrowsePanel::Streams => {
+                    if matches!(direction, MoveDirection::Down) && self.streams.wants_more() {
+                        if !self.streams.loading {
+                            self.streams.loading = true;
+                            let _result = tx.send(Event::LoadStreams);
+                        }
+                    } else {
+                        self.streams.cycle(&direction);
+                    }
+                }
+            },
+            Event::HomeEndHighlight(end) => match self.focused_panel {
+                BrowsePanel::Categories => self.categories.jump(&end),
+                BrowsePanel::Streams => self.streams.jump(&end),
+            },
+            Event::Selected => match self.focused_panel {
+                BrowsePanel::Categories => {
+                    if let Some(category) = self.categories.items.get(self.categories.highlight) {
+                        self.open_category = Some(category.clone());
+                        self.streams = Paged::new();
+                        self.streams.loading = true;
+                        self.focused_panel = BrowsePanel::Streams;
+
+                        let _result = tx.send(Event::LoadStreams);
+                    }
+                }
+                BrowsePanel::Streams => {
+                    if self.streams.items.get(self.streams.highlight).is_some() {
+                        player_popup(tx, &self.players.choices(None));
+                    }
+                }
+            },
+            Event::CyclePanel(direction) => {
+                self.focused_panel = match direction {
+                    MoveDirection::Left => self.focused_panel.left(),
+                    MoveDirection::Right => match self.open_category {
+                        Some(_) => self.focused_panel.right(),
+                        None => self.focused_panel,
+                    },
+                    _ => self.focused_panel,
+                };
+            }
+            _ => {}
+        }
+    }
+}
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use crossterm::event::KeyEvent;
 use tokio::sync::mpsc::UnboundedSender;
 use tui::{backend::Backend, terminal::Frame};
-use twitch::account::Account;
+use twitch::{account::Account, status_cache::StatusCache};
 use ui::theme::Theme;
 
 pub struct Exit;
@@ -21,19 +21,20 @@ impl State for Exit {
         let _result = tx.send(Event::Exited);
     }
 
-    fn render<B: Backend>(&self, _: &Theme, _: &mut Frame<'_, B>, _: u64) {}
+    fn render<B: Backend>(&mut self, _: &Theme, _: &mut Frame<'_, B>, _: u64) {}
 
     fn transition(
-        &self,
+        &mut self,
         _: Event,
         _: &Option<Account>,
+        _: &StatusCache,
         _: UnboundedSender<Event>,
     ) -> Option<Transition> {
         None
     }
 
-    fn handle(&self, _: KeyEvent) -> Option<Event> {
-        None
+    fn handle(&mut self, _: KeyEvent) -> Vec<Event> {
+        Vec::new()
     }
 
     fn process(&mut self, _: Event, _: &UnboundedSender<Event>) {}
@@ -0,0 +1,319 @@
+use std::mem::swap;
+
+use async_trait::async_trait;
+use crossterm::event::{KeyEvent, MouseEvent};
+use input::{handler::Handler, mouse::MouseHandler};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tui::{backend::Backend, terminal::Frame};
+use twitch::{
+    account::Account,
+    channel::Channel,
+    follows::{self, FollowsUpdate},
+    player::Players,
+    status_cache::StatusCache,
+    status_service::SharedService,
+};
+use ui::{column::Column, hit::Hits, render, theme::Theme};
+
+use crate::{
+    app_state::{
+        browse::Browse,
+        exit::Exit,
+        lists::Lists,
+        open_tab,
+        popup::{player_popup, theme_popup, Popup},
+        FOLLOWS_TAB,
+    },
+    event::Event,
+    input_mappings::follows_inputs,
+    launch::{self, PendingLaunch},
+    mouse::{gesture, Gesture},
+    refresh::Refresh,
+    settings::Settings,
+    state::{AppState, MoveDirection, MoveEnd, State},
+    transition::Transition,
+    util::{index_add, index_subtract},
+};
+
+pub struct Follows {
+    channels: Vec<Channel>,
+    highlight: usize,
+    loading: bool,
+    error: Option<String>,
+    follows_check: UnboundedReceiver<FollowsUpdate>,
+    follows_check_sender: UnboundedSender<FollowsUpdate>,
+    service: SharedService,
+    input_handler: Handler<Event>,
+    players: Players,
+    pending_launch: Option<PendingLaunch>,
+    refresh: Refresh,
+    columns: Vec<Column>,
+    hits: Hits<()>,
+    mouse: MouseHandler,
+}
+
+impl Follows {
+    pub fn new(
+        channels: &[Channel],
+        highlight: usize,
+        players: &Players,
+        refresh: Refresh,
+        columns: &[Column],
+    ) -> Self {
+        let (sender, receiver) = unbounded_channel();
+
+        Self {
+            channels: channels.to_vec(),
+            highlight,
+            loading: false,
+            error: None,
+            follows_check: receiver,
+            follows_check_sender: sender,
+            service: SharedService::default(),
+            input_handler: Handler::new(follows_inputs()),
+            players: players.clone(),
+            pending_launch: None,
+            refresh,
+            columns: columns.to_vec(),
+            hits: Hits::default(),
+            mouse: MouseHandler::default(),
+        }
+    }
+
+    pub fn from_existing(state: &mut Self) -> Self {
+        state.follows_check();
+
+        let mut follows = Self::new(
+            &state.channels,
+            state.highlight,
+            &state.players,
+            state.refresh,
+            &state.columns,
+        );
+
+        follows.loading = state.loading;
+        follows.error = state.error.take();
+        follows.pending_launch = state.pending_launch.take();
+        follows.service = state.service.clone();
+
+        // A fetch still in flight was sent to the old receiver
+        swap(&mut follows.follows_check, &mut state.follows_check);
+        swap(
+            &mut follows.follows_check_sender,
+            &mut state.follows_check_sender,
+        );
+
+        follows
+    }
+
+    pub fn init(tx: &UnboundedSender<Event>) -> Self {
+        let _result = tx.send(Event::CheckFollows);
+
+        let settings = Settings::load();
+
+        let mut follows = Self::new(
+            &Vec::new(),
+            0,
+            &Players::load(),
+            Refresh::new(settings.refresh_interval),
+            &settings.columns,
+        );
+
+        follows.loading = true;
+
+        follows
+    }
+
+    pub fn follows_check(&mut self) {
+        while let Ok(update) = self.follows_check.try_recv() {
+            self.loading = false;
+
+            match update {
+                Ok(channels) => {
+                    // Keep the same channel highlighted as the order changes
+                    let highlighted = self
+                        .channels
+                        .get(self.highlight)
+                        .map(|channel| channel.handle.clone());
+
+                    self.channels = channels;
+                    self.error = None;
+                    self.highlight = highlighted
+                        .and_then(|handle| {
+                            self.channels
+                                .iter()
+                                .position(|channel| channel.handle == handle)
+                        })
+                        .unwrap_or(0);
+
+                    self.refresh.updated();
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl State for Follows {
+    #[allow(clippy::ignored_unit_patterns)]
+    async fn tick(&self, _: &Option<Account>, _: u64, tx: UnboundedSender<Event>) {
+        if self.refresh.due() {
+            let _result = tx.send(Event::RefreshChannels);
+        }
+    }
+
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+        self.hits = render::follows(
+            theme,
+            frame,
+            self.highlight,
+            &self.channels,
+            &self.columns,
+            self.loading,
+            self.error.as_deref(),
+            &self.input_handler.render(),
+            self.refresh.since_update(),
+            timer,
+        );
+    }
+
+    fn transition(
+        &mut self,
+        event: Event,
+        account: &Option<Account>,
+        statuses: &StatusCache,
+        tx: UnboundedSender<Event>,
+    ) -> Option<Transition> {
+        match event {
+            Event::Exited => Some(Transition::To(AppState::Exit(Exit::new()))),
+            Event::CheckFollows => {
+                if let Some(acc) = account {
+                    self.loading = true;
+
+                    follows::fetch(acc, &self.service, statuses, &self.follows_check_sender);
+                }
+
+                None
+            }
+            Event::RefreshChannels => {
+                // Several of these can be queued before the first one is handled
+                if !self.refresh.due() {
+                    return None;
+                }
+
+                self.refresh.schedule();
+
+                if let Some(acc) = account {
+                    follows::fetch(acc, &self.service, statuses, &self.follows_check_sender);
+                }
+
+                None
+            }
+            Event::ChoicePopupStarted((title, message, options, callback)) => {
+                Some(Transition::Push(AppState::Popup(Popup::new_choice(
+                    title, message, &options, callback,
+                ))))
+            }
+            Event::InputPopupStarted((title, message, callback)) => Some(Transition::Push(
+                AppState::Popup(Popup::new_input(title, message, callback)),
+            )),
+            Event::TimedInfoPopupStarted((title, message, duration, callback)) => {
+                Some(Transition::Push(AppState::Popup(Popup::new_timed_info(
+                    title, message, duration, callback,
+                ))))
+            }
+            Event::PlayerChoice(choice) => {
+                if let Some(channel) = self.channels.get(self.highlight) {
+                    self.pending_launch = launch::player_chosen(
+                        channel,
+                        channel.player.as_deref(),
+                        &self.players,
+                        choice,
+                        &tx,
+                    );
+                }
+
+                None
+            }
+            Event::QualitiesFetched(id, qualities) => {
+                launch::qualities_fetched(&mut self.pending_launch, id, qualities, &tx);
+
+                None
+            }
+            Event::QualityChoice(choice) => {
+                // Followed channels aren't saved anywhere, so the choice isn't remembered
+                let _channel = launch::quality_chosen(&mut self.pending_launch, choice, &tx);
+
+                None
+            }
+            Event::ChannelSelected(channel, player, chat) => {
+                launch::open(&channel, &player, chat, &tx);
+
+                None
+            }
+            Event::SelectTab(tab) => open_tab(tab, FOLLOWS_TAB, statuses, &tx),
+            Event::CycleTab(MoveDirection::Left) => {
+                Some(Transition::To(AppState::Lists(Lists::init(statuses, &tx))))
+            }
+            Event::CycleTab(MoveDirection::Right) => {
+                Some(Transition::To(AppState::Browse(Browse::init(&tx))))
+            }
+            _ => None,
+        }
+    }
+
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
+        self.input_handler.handle(key_event)
+    }
+
+    fn flush(&mut self) -> Vec<Event> {
+        self.input_handler.flush()
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        match gesture(&mut self.mouse, &self.hits, mouse_event) {
+            Some(Gesture::Tab(tab)) => vec![Event::SelectTab(tab)],
+            Some(Gesture::Highlight((), index)) => {
+                self.highlight = index;
+
+                Vec::new()
+            }
+            Some(Gesture::Open((), index)) => {
+                self.highlight = index;
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Scroll((), direction)) => vec![Event::CycleHighlight(direction)],
+            Some(Gesture::Focus(()) | Gesture::Choose(_)) | None => Vec::new(),
+        }
+    }
+
+    fn process(&mut self, action: Event, tx: &UnboundedSender<Event>) {
+        match action {
+            Event::Exited | Event::CycleTab(_) | Event::SelectTab(_) | Event::ShowNotifications => {
+                let _result = tx.send(action);
+            }
+            Event::PickTheme => theme_popup(tx),
+            Event::CycleHighlight(direction) => {
+                self.highlight = match direction {
+                    MoveDirection::Down => index_add(self.highlight, self.channels.len()),
+                    MoveDirection::Up => index_subtract(self.highlight, self.channels.len()),
+                    _ => self.highlight,
+                };
+            }
+            Event::HomeEndHighlight(end) => {
+                self.highlight = match end {
+                    MoveEnd::First => 0,
+                    MoveEnd::Last => self.channels.len().saturating_sub(1),
+                };
+            }
+            Event::Selected => {
+                if let Some(channel) = self.channels.get(self.highlight) {
+                    player_popup(tx, &self.players.choices(channel.player.as_deref()));
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,27 +1,44 @@
 use crate::{
     app_state::{
+        browse::Browse,
         exit::Exit,
-        popup::{chat_popup, chat_popup_search, Popup},
+        open_tab,
+        popup::{
+            add_popup, player_popup, player_popup_search, remove_popup, rename_popup, theme_popup,
+            Popup,
+        },
+        HOME_TAB,
     },
     event::Event,
     input_mappings::{home_inputs, typing_inputs},
+    launch::{self, PendingLaunch},
+    mouse::{gesture, Gesture},
+    refresh::Refresh,
+    search::Search,
+    settings::Settings,
     state::{AppState, MoveDirection, MoveEnd, State},
+    toast::{notify, notify_toggled},
     transition::Transition,
     util::{index_add, index_subtract},
 };
 use async_trait::async_trait;
-use crossterm::event::{KeyCode, KeyEvent};
-use input::handler::Handler;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use input::{handler::Handler, mouse::MouseHandler};
+use std::mem::swap;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::error;
 use tui::{backend::Backend, terminal::Frame};
 use twitch::{
-    account::Account,
-    channel::{status::Status, Channel},
+    account::Account, channel::Channel, player::Players, status_cache::StatusCache,
+    status_service::StatusUpdate,
 };
 use ui::{
+    column::Column,
+    hit::Hits,
     panel::{Home as HomePanel, Panel},
     render,
     theme::Theme,
+    toast::Severity,
 };
 
 use super::lists::Lists;
@@ -29,29 +46,40 @@ use super::lists::Lists;
 pub struct Home {
     channel_highlight: usize,
     pub favourites: Vec<Channel>,
-    channel_check: UnboundedReceiver<(String, (Status, Option<String>))>,
-    channel_check_sender: UnboundedSender<(String, (Status, Option<String>))>,
+    channel_check: UnboundedReceiver<StatusUpdate>,
+    channel_check_sender: UnboundedSender<StatusUpdate>,
     typing: bool,
     search_input: Vec<char>,
     focused_panel: HomePanel,
     input_handler: Handler<Event>,
+    players: Players,
+    pending_launch: Option<PendingLaunch>,
+    refresh: Refresh,
+    columns: Vec<Column>,
+    search: Search,
+    hits: Hits<HomePanel>,
+    mouse: MouseHandler,
 }
 
 impl Home {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         channel_highlight: usize,
         favourites: &[Channel],
         typing: bool,
         search_input: &[char],
         focused_panel: HomePanel,
+        players: &Players,
+        refresh: Refresh,
+        columns: &[Column],
         _: &UnboundedSender<Event>,
     ) -> Self {
         let (sender, receiver) = unbounded_channel();
 
-        let inputs = if typing {
-            typing_inputs()
+        let input_handler = if typing {
+            Handler::without_counts(typing_inputs())
         } else {
-            home_inputs()
+            Handler::new(home_inputs())
         };
 
         Self {
@@ -62,98 +90,222 @@ impl Home {
             typing,
             search_input: search_input.to_vec(),
             focused_panel,
-            input_handler: Handler::new(inputs),
+            input_handler,
+            players: players.clone(),
+            pending_launch: None,
+            refresh,
+            columns: columns.to_vec(),
+            search: Search::new(),
+            hits: Hits::default(),
+            mouse: MouseHandler::default(),
         }
     }
 
     pub fn from_existing(state: &mut Self, tx: &UnboundedSender<Event>) -> Self {
-        let mut channels = state.favourites.clone();
+        state.channel_check();
 
-        while let Ok((handle, (status, game_name))) = state.channel_check.try_recv() {
-            if let Some(index) = state
-                .favourites
-                .iter()
-                .position(|channel| channel.handle == handle && channel.status != status)
-            {
-                channels[index].status = status;
-                channels[index].game = game_name;
-            }
-        }
-
-        Self::new(
+        let mut home = Self::new(
             state.channel_highlight,
-            &channels,
+            &state.favourites,
             state.typing,
             &state.search_input,
             state.focused_panel,
+            &state.players,
+            state.refresh,
+            &state.columns,
+            tx,
+        );
+
+        home.pending_launch = state.pending_launch.take();
+
+        // Checks and searches still in flight were sent to the old receivers
+        swap(&mut home.search, &mut state.search);
+        swap(&mut home.channel_check, &mut state.channel_check);
+        swap(
+            &mut home.channel_check_sender,
+            &mut state.channel_check_sender,
+        );
+
+        home
+    }
+
+    pub fn init(
+        favourites: &[Channel],
+        statuses: &StatusCache,
+        tx: &UnboundedSender<Event>,
+    ) -> Self {
+        let mut channels = favourites.to_vec();
+
+        let stale = statuses.apply(&mut channels);
+
+        let settings = Settings::load();
+
+        if !stale.is_empty() {
+            let _result = tx.send(Event::CheckChannels(stale));
+        }
+
+        Self::new(
+            0,
+            &channels,
+            false,
+            &Vec::new(),
+            HomePanel::default(),
+            &Players::load(),
+            Refresh::new(settings.refresh_interval),
+            &settings.columns,
             tx,
         )
     }
 
-    pub fn init(favourites: &[Channel], tx: &UnboundedSender<Event>) -> Self {
-        let mut channels_awaiting: Vec<Channel> = Vec::new();
+    fn remember_quality(&mut self, chosen: &Channel, tx: &UnboundedSender<Event>) {
+        match self
+            .favourites
+            .iter_mut()
+            .find(|channel| channel.handle == chosen.handle)
+        {
+            Some(channel) if channel.quality != chosen.quality => {
+                channel.quality = chosen.quality.clone();
+            }
+            _ => return,
+        }
+
+        self.save_favourites(tx);
+    }
 
-        for channel in favourites {
-            if channel.status == Status::Awaiting {
-                channels_awaiting.push(channel.clone());
+    fn save_favourites(&self, tx: &UnboundedSender<Event>) {
+        match Channel::save_to_file(&Channel::favourites_file(), &self.favourites) {
+            Ok(()) => {
+                let _result = tx.send(Event::ChannelsSaved);
             }
+            Err(e) => error!("Could not save favourites: {e}"),
         }
+    }
 
-        if !channels_awaiting.is_empty() {
-            let _result = tx.send(Event::CheckChannels(channels_awaiting));
+    // Whether the channel was added, it isn't if it's already a favourite
+    fn add_favourite(&mut self, channel: Channel, tx: &UnboundedSender<Event>) -> bool {
+        if self
+            .favourites
+            .iter()
+            .any(|favourite| favourite.handle.eq_ignore_ascii_case(&channel.handle))
+        {
+            notify(
+                tx,
+                Severity::Warning,
+                "Already Added",
+                &format!("{} is already a favourite", channel.friendly_name),
+            );
+
+            return false;
         }
 
-        Self::new(0, favourites, false, &Vec::new(), HomePanel::default(), tx)
+        self.favourites.push(channel);
+        self.channel_highlight = self.favourites.len() - 1;
+        self.save_favourites(tx);
+
+        true
     }
 
-    pub fn channel_check(&mut self) {
-        let mut channels = self.favourites.clone();
+    fn highlight(&mut self, panel: HomePanel, index: usize) {
+        self.focused_panel = panel;
 
-        while let Ok((handle, (status, game_name))) = self.channel_check.try_recv() {
-            if let Some(index) = self
-                .favourites
-                .iter()
-                .position(|channel| channel.handle == handle && channel.status != status)
-            {
-                channels[index].status = status;
-                channels[index].game = game_name;
+        match panel {
+            HomePanel::Favourites => self.channel_highlight = index,
+            HomePanel::Search => self.search.highlight = Some(index),
+        }
+    }
+
+    pub fn channel_check(&mut self) {
+        while let Ok(update) = self.channel_check.try_recv() {
+            for channel in &mut self.favourites {
+                if channel.apply(&update) {
+                    break;
+                }
             }
+
+            self.refresh.updated();
         }
+    }
 
-        self.favourites = channels;
+    pub fn search_check(&mut self) {
+        self.search.receive();
     }
 }
 
 #[async_trait]
 impl State for Home {
     #[allow(clippy::ignored_unit_patterns)]
-    async fn tick(&self, _: &Option<Account>, _: u64, _: UnboundedSender<Event>) {}
+    async fn tick(&self, _: &Option<Account>, _: u64, tx: UnboundedSender<Event>) {
+        if self.refresh.due() {
+            let _result = tx.send(Event::RefreshChannels);
+        }
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, _: u64) {
-        render::home(
+        if self.search.due() {
+            let _result = tx.send(Event::SearchChannels);
+        }
+    }
+
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, _: u64) {
+        self.hits = render::home(
             theme,
             frame,
             &self.channel_highlight,
             &self.favourites,
+            &self.columns,
             self.typing,
             &self.search_input,
+            &self.search.results,
+            self.search.highlight,
+            self.search.message().as_deref(),
             &self.focused_panel,
             &self.input_handler.render(),
+            self.refresh.since_update(),
         );
     }
 
     #[allow(clippy::too_many_lines)]
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         account: &Option<Account>,
+        statuses: &StatusCache,
         tx: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match event {
             Event::Exited => Some(Transition::To(AppState::Exit(Exit::new()))),
             Event::CheckChannels(channels) => {
                 if let Some(acc) = account {
-                    Channel::check(&channels, acc, &self.channel_check_sender);
+                    Channel::check(&channels, acc, statuses, &self.channel_check_sender);
+                }
+
+                None
+            }
+            Event::StatusChanged(update) => {
+                let _result = self.channel_check_sender.send(update);
+
+                None
+            }
+            Event::RefreshChannels => {
+                // Several of these can be queued before the first one is handled
+                if !self.refresh.due() {
+                    return None;
+                }
+
+                self.refresh.schedule();
+
+                if let Some(acc) = account {
+                    if !self.favourites.is_empty() {
+                        Channel::check(&self.favourites, acc, statuses, &self.channel_check_sender);
+                    }
+                }
+
+                None
+            }
+            Event::SearchChannels => {
+                // Typing may have carried on since this was queued
+                if self.search.due() {
+                    let query: String = self.search_input.iter().collect();
+
+                    self.search.start(&query, account, statuses.rate_limit());
                 }
 
                 None
@@ -171,101 +323,247 @@ impl State for Home {
                     title, message, duration, callback,
                 ))))
             }
-            Event::ChatChoice(choice) => {
-                let chat_choice = choice == 1;
-
+            Event::PlayerChoice(choice) => {
                 if let Some(channel) = self.favourites.get(self.channel_highlight) {
-                    let _result = tx.send(Event::ChannelSelected((*channel).clone(), chat_choice));
+                    self.pending_launch = launch::player_chosen(
+                        channel,
+                        channel.player.as_deref(),
+                        &self.players,
+                        choice,
+                        &tx,
+                    );
                 }
 
                 None
             }
-            Event::ChatChoiceSearch(choice) => {
-                let chat_choice = choice == 1;
+            Event::PlayerChoiceSearch(choice) => {
+                if let Some(channel) = self.search.selected() {
+                    self.pending_launch =
+                        launch::player_chosen(channel, None, &self.players, choice, &tx);
+                }
+
+                None
+            }
+            Event::EntryAdded(handle) => {
+                let handle = handle.trim().to_owned();
 
-                // TODO check if channel exists?
-                // TODO check if channel is online?
-                let handle: String = self.search_input.iter().collect();
+                if handle.is_empty() {
+                    return None;
+                }
 
-                let channel = Channel::new(handle.clone(), handle, None);
+                let channel = Channel::new(handle.clone(), handle);
 
-                let _result = tx.send(Event::ChannelSelected(channel, chat_choice));
+                if self.add_favourite(channel.clone(), &tx) {
+                    let _result = tx.send(Event::CheckChannels(vec![channel]));
+                }
 
                 None
             }
-            Event::CycleTab(direction) => match direction {
-                MoveDirection::Left | MoveDirection::Right => {
-                    // TODO shouldn't be reloading this every time
-                    Some(Transition::To(AppState::Lists(Lists::init(&tx))))
-                }
-                _ => None,
-            },
-            Event::ChannelSelected(channel, chat) => {
-                if let Err(e) = channel.launch() {
-                    eprintln!("Error opening stream: {e}");
+            Event::RemoveConfirmed(0) => {
+                if self.channel_highlight < self.favourites.len() {
+                    self.favourites.remove(self.channel_highlight);
+                    self.channel_highlight = self
+                        .channel_highlight
+                        .min(self.favourites.len().saturating_sub(1));
+                    self.save_favourites(&tx);
                 }
 
-                if chat {
-                    if let Err(e) = channel.launch_chat() {
-                        eprintln!("Error opening chat: {e}");
+                None
+            }
+            Event::EntryRenamed(name) => {
+                let name = name.trim();
+
+                if let Some(channel) = self.favourites.get_mut(self.channel_highlight) {
+                    if !name.is_empty() {
+                        channel.friendly_name = name.to_owned();
+                        self.save_favourites(&tx);
                     }
                 }
 
                 None
             }
+            Event::QualitiesFetched(id, qualities) => {
+                launch::qualities_fetched(&mut self.pending_launch, id, qualities, &tx);
+
+                None
+            }
+            Event::QualityChoice(choice) => {
+                if let Some(channel) = launch::quality_chosen(&mut self.pending_launch, choice, &tx)
+                {
+                    self.remember_quality(&channel, &tx);
+                }
+
+                None
+            }
+            Event::CycleTab(MoveDirection::Left) => {
+                Some(Transition::To(AppState::Browse(Browse::init(&tx))))
+            }
+            Event::CycleTab(MoveDirection::Right) => {
+                // TODO shouldn't be reloading this every time
+                Some(Transition::To(AppState::Lists(Lists::init(statuses, &tx))))
+            }
+            Event::SelectTab(tab) => open_tab(tab, HOME_TAB, statuses, &tx),
+            Event::ChannelSelected(channel, player, chat) => {
+                launch::open(&channel, &player, chat, &tx);
+
+                None
+            }
             _ => None,
         }
     }
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event> {
-        let action = self.input_handler.handle(key_event);
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
+        let actions = self.input_handler.handle(key_event);
 
-        if self.typing && action.is_none() {
+        if self.typing && actions.is_empty() {
             if let KeyCode::Char(char) = key_event.code {
-                return Some(Event::Typed(char));
+                return vec![Event::Typed(char)];
             }
         }
 
-        action
+        actions
+    }
+
+    fn flush(&mut self) -> Vec<Event> {
+        self.input_handler.flush()
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        // The search box keeps the keys, and the mouse, until typing stops
+        if self.typing {
+            return Vec::new();
+        }
+
+        match gesture(&mut self.mouse, &self.hits, mouse_event) {
+            Some(Gesture::Tab(tab)) => vec![Event::SelectTab(tab)],
+            Some(Gesture::Focus(HomePanel::Search)) => {
+                self.focused_panel = HomePanel::Search;
+                self.search.highlight = None;
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Focus(panel)) => {
+                self.focused_panel = panel;
+
+                Vec::new()
+            }
+            Some(Gesture::Highlight(panel, index)) => {
+                self.highlight(panel, index);
+
+                Vec::new()
+            }
+            Some(Gesture::Open(panel, index)) => {
+                self.highlight(panel, index);
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Scroll(panel, direction)) => {
+                self.focused_panel = panel;
+
+                vec![Event::CycleHighlight(direction)]
+            }
+            Some(Gesture::Choose(_)) | None => Vec::new(),
+        }
     }
 
     fn process(&mut self, action: Event, tx: &UnboundedSender<Event>) {
         match action {
-            Event::Exited | Event::CycleTab(_) => {
+            Event::Exited | Event::CycleTab(_) | Event::SelectTab(_) | Event::ShowNotifications => {
                 let _result = tx.send(action);
             }
-            Event::CycleHighlight(direction) => {
-                if self.focused_panel == HomePanel::Favourites {
-                    self.channel_highlight = match direction {
-                        MoveDirection::Down => {
-                            index_add(self.channel_highlight, self.favourites.len())
-                        }
-                        MoveDirection::Up => {
-                            index_subtract(self.channel_highlight, self.favourites.len())
-                        }
-                        _ => self.channel_highlight,
-                    };
-                }
+            Event::PickTheme => theme_popup(tx),
+            Event::CycleHighlight(direction) if self.focused_panel == HomePanel::Favourites => {
+                self.channel_highlight = match direction {
+                    MoveDirection::Down => index_add(self.channel_highlight, self.favourites.len()),
+                    MoveDirection::Up => {
+                        index_subtract(self.channel_highlight, self.favourites.len())
+                    }
+                    _ => self.channel_highlight,
+                };
             }
-            Event::HomeEndHighlight(end) => {
-                if self.focused_panel == HomePanel::Favourites {
-                    self.channel_highlight = match end {
-                        MoveEnd::First => 0,
-                        MoveEnd::Last => self.favourites.len() - 1,
-                    };
-                }
+            Event::CycleHighlight(direction) if self.focused_panel == HomePanel::Search => {
+                // Going up from the first result gets back to the search box
+                self.search.highlight = match (direction, self.search.highlight) {
+                    (MoveDirection::Down, None) if !self.search.results.is_empty() => Some(0),
+                    (MoveDirection::Down, Some(index)) => {
+                        Some(index_add(index, self.search.results.len()))
+                    }
+                    (MoveDirection::Up, Some(0)) => None,
+                    (MoveDirection::Up, Some(index)) => Some(index - 1),
+                    (_, highlight) => highlight,
+                };
+            }
+            Event::HomeEndHighlight(end) if self.focused_panel == HomePanel::Favourites => {
+                self.channel_highlight = match end {
+                    MoveEnd::First => 0,
+                    MoveEnd::Last => self.favourites.len().saturating_sub(1),
+                };
             }
             Event::Selected => match self.focused_panel {
                 HomePanel::Favourites => {
-                    if self.favourites.get(self.channel_highlight).is_some() {
-                        chat_popup(tx);
+                    if let Some(channel) = self.favourites.get(self.channel_highlight) {
+                        player_popup(tx, &self.players.choices(channel.player.as_deref()));
                     }
                 }
                 HomePanel::Search => {
-                    self.typing = true;
-                    self.input_handler = Handler::new(typing_inputs());
+                    if self.search.selected().is_some() {
+                        player_popup_search(tx, &self.players.choices(None));
+                    } else {
+                        self.typing = true;
+                        self.input_handler = Handler::without_counts(typing_inputs());
+                    }
+                }
+            },
+            Event::AddEntry => match self.focused_panel {
+                HomePanel::Favourites => add_popup(
+                    tx,
+                    "Add Favourite",
+                    "Which channel do you want to add? Enter its handle",
+                ),
+                HomePanel::Search => {
+                    // The highlighted result, or whatever was typed if none is
+                    if let Some(channel) = self.search.selected() {
+                        self.add_favourite(channel.clone(), tx);
+                    } else {
+                        let handle: String = self.search_input.iter().collect();
+
+                        if !handle.trim().is_empty() {
+                            let _result = tx.send(Event::EntryAdded(handle));
+                        }
+                    }
                 }
             },
+            Event::RemoveEntry if self.focused_panel == HomePanel::Favourites => {
+                if let Some(channel) = self.favourites.get(self.channel_highlight) {
+                    remove_popup(tx, &channel.friendly_name, "favourites");
+                }
+            }
+            Event::MoveEntry(direction) if self.focused_panel == HomePanel::Favourites => {
+                let target = match direction {
+                    MoveDirection::Up => self.channel_highlight.checked_sub(1),
+                    MoveDirection::Down => Some(self.channel_highlight + 1),
+                    _ => None,
+                };
+
+                if let Some(target) = target.filter(|target| *target < self.favourites.len()) {
+                    self.favourites.swap(self.channel_highlight, target);
+                    self.channel_highlight = target;
+                    self.save_favourites(tx);
+                }
+            }
+            Event::RenameEntry if self.focused_panel == HomePanel::Favourites => {
+                if let Some(channel) = self.favourites.get(self.channel_highlight) {
+                    rename_popup(tx, &channel.friendly_name);
+                }
+            }
+            Event::ToggleNotify if self.focused_panel == HomePanel::Favourites => {
+                if let Some(channel) = self.favourites.get_mut(self.channel_highlight) {
+                    channel.notify = !channel.notify;
+
+                    notify_toggled(tx, channel);
+                    self.save_favourites(tx);
+                }
+            }
             Event::CyclePanel(direction) => {
                 self.focused_panel = match direction {
                     MoveDirection::Left => self.focused_panel.left(),
@@ -278,24 +576,30 @@ impl State for Home {
                 self.input_handler = Handler::new(home_inputs());
             }
             Event::Submit => {
-                if self.search_input.is_empty() {
-                    return;
-                }
-
+                // Jump to the results to pick one
                 self.typing = false;
                 self.input_handler = Handler::new(home_inputs());
-                chat_popup_search(tx);
+
+                if self.search.hurry() {
+                    let _result = tx.send(Event::SearchChannels);
+                } else if !self.search.results.is_empty() {
+                    self.search.highlight = Some(0);
+                }
             }
             Event::DeleteChar => {
                 self.search_input.pop();
+                self.search.typed();
             }
             Event::Typed(char) => {
                 self.search_input.push(char);
+                self.search.typed();
             }
             Event::Paste(to_paste) => {
                 for c in to_paste.chars() {
                     self.search_input.push(c);
                 }
+
+                self.search.typed();
             }
             _ => {}
         }
@@ -1,37 +1,60 @@
-use std::fs::read_to_string;
+use std::{
+    fs::{create_dir_all, rename},
+    mem::swap,
+};
 
 use async_trait::async_trait;
-use crossterm::event::KeyEvent;
-use input::handler::Handler;
-use jwalk::WalkDir;
+use crossterm::event::{KeyEvent, MouseEvent};
+use input::{handler::Handler, mouse::MouseHandler};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::error;
 use tui::{backend::Backend, terminal::Frame};
 use twitch::{
     account::Account,
-    channel::{status::Status, Channel, List},
+    channel::{Channel, List, LIST_FILE_EXTENSION},
+    player::Players,
+    status_cache::StatusCache,
+    status_service::StatusUpdate,
+    storage,
 };
 use ui::{
+    column::Column,
+    hit::Hits,
     panel::{Lists as ListsPanel, Panel},
     render,
     theme::Theme,
+    toast::Severity,
 };
 
 use crate::{
     app_state::{
         exit::Exit,
+        follows::Follows,
         home::Home,
-        popup::{chat_popup, Popup},
+        open_tab,
+        popup::{
+            add_popup, delete_popup, list_popup, player_popup, remove_popup, rename_popup,
+            theme_popup, Popup,
+        },
+        LISTS_TAB,
     },
     event::Event,
     input_mappings::lists_inputs,
+    launch::{self, PendingLaunch},
+    mouse::{gesture, Gesture},
+    refresh::Refresh,
+    settings::Settings,
     state::{AppState, MoveDirection, MoveEnd, State},
+    toast::{notify, notify_toggled},
     transition::Transition,
     util::{index_add, index_subtract},
 };
 
-const CHANNELS_FILE: &str = "favourites.json";
-const LISTS_PATH: &str = "lists";
-const LISTS_FILE_EXTENSION: &str = ".json";
+// A channel waiting on the list it should be copied or moved to
+struct Transfer {
+    moving: bool,
+    targets: Vec<usize>,
+}
 
 pub struct Lists {
     lists: Vec<List>,
@@ -40,8 +63,15 @@ pub struct Lists {
     focused_panel: ListsPanel,
     open_list: Option<usize>,
     channel_highlight: usize,
-    channel_check: UnboundedReceiver<(String, (Status, Option<String>))>,
-    channel_check_sender: UnboundedSender<(String, (Status, Option<String>))>,
+    channel_check: UnboundedReceiver<StatusUpdate>,
+    channel_check_sender: UnboundedSender<StatusUpdate>,
+    players: Players,
+    pending_launch: Option<PendingLaunch>,
+    refresh: Refresh,
+    columns: Vec<Column>,
+    transfer: Option<Transfer>,
+    hits: Hits<ListsPanel>,
+    mouse: MouseHandler,
 }
 
 impl Lists {
@@ -50,6 +80,9 @@ impl Lists {
         lists: &[List],
         open_list: Option<usize>,
         channel_highlight: usize,
+        players: &Players,
+        refresh: Refresh,
+        columns: &[Column],
     ) -> Self {
         let (sender, receiver) = unbounded_channel();
 
@@ -62,99 +95,330 @@ impl Lists {
             channel_highlight,
             channel_check: receiver,
             channel_check_sender: sender,
+            players: players.clone(),
+            pending_launch: None,
+            refresh,
+            columns: columns.to_vec(),
+            transfer: None,
+            hits: Hits::default(),
+            mouse: MouseHandler::default(),
         }
     }
 
     pub fn from_existing(state: &mut Self) -> Self {
-        let mut updated_lists = state.lists.clone();
+        state.channel_check();
+
+        let mut lists = Self::new(
+            state.highlight,
+            &state.lists,
+            state.open_list,
+            state.channel_highlight,
+            &state.players,
+            state.refresh,
+            &state.columns,
+        );
+
+        lists.focused_panel = state.focused_panel;
+        lists.pending_launch = state.pending_launch.take();
+        lists.transfer = state.transfer.take();
+
+        // Checks still in flight were sent to the old receiver
+        swap(&mut lists.channel_check, &mut state.channel_check);
+        swap(
+            &mut lists.channel_check_sender,
+            &mut state.channel_check_sender,
+        );
+
+        lists
+    }
 
-        for (index, list) in state.lists.iter_mut().enumerate() {
-            let mut channels = list.channels.clone();
+    pub fn init(statuses: &StatusCache, tx: &UnboundedSender<Event>) -> Self {
+        let mut lists = List::load_all();
 
-            while let Ok((handle, (status, game_name))) = state.channel_check.try_recv() {
-                if let Some(index) = list
-                    .channels
+        // Checked all at once so every list shares the same batched requests
+        let mut channels_stale: Vec<Channel> = Vec::new();
+
+        for list in &mut lists {
+            for channel in statuses.apply(&mut list.channels) {
+                if !channels_stale
                     .iter()
-                    .position(|channel| channel.handle == handle && channel.status != status)
+                    .any(|stale| stale.handle == channel.handle)
                 {
-                    channels[index].status = status;
-                    channels[index].game = game_name;
+                    channels_stale.push(channel);
                 }
             }
+        }
 
-            updated_lists[index].channels = channels;
+        if !channels_stale.is_empty() {
+            let _result = tx.send(Event::CheckChannels(channels_stale));
         }
 
+        let settings = Settings::load();
+
         Self::new(
-            state.highlight,
-            &updated_lists,
-            state.open_list,
-            state.channel_highlight,
+            0,
+            &lists,
+            None,
+            0,
+            &Players::load(),
+            Refresh::new(settings.refresh_interval),
+            &settings.columns,
         )
     }
 
-    pub fn init(tx: &UnboundedSender<Event>) -> Self {
-        let lists = WalkDir::new(LISTS_PATH)
-            .sort(true)
-            .into_iter()
-            .filter_map(Result::ok)
-            .filter(|entry| {
-                entry
-                    .file_name
-                    .to_str()
-                    .unwrap_or("")
-                    .ends_with(LISTS_FILE_EXTENSION)
-            })
-            .map(|entry| (entry.path(), entry.file_name.into_string().ok()))
-            .map(|(file_path, file_name)| {
-                // TODO error handling here needs doing
-                let data: String = read_to_string(file_path).unwrap_or_default();
-
-                let channels: Vec<Channel> =
-                    serde_json::from_str(data.as_str()).unwrap_or_default();
-
-                let mut channels_awaiting: Vec<Channel> = Vec::new();
-
-                for channel in &channels {
-                    if channel.status == Status::Awaiting {
-                        channels_awaiting.push((*channel).clone());
-                    }
-                }
+    fn highlight(&mut self, panel: ListsPanel, index: usize) {
+        self.focused_panel = panel;
+
+        match panel {
+            ListsPanel::Lists => self.highlight = index,
+            ListsPanel::ListContent => self.channel_highlight = index,
+        }
+    }
 
-                if !channels_awaiting.is_empty() {
-                    let _result = tx.send(Event::CheckChannels(channels_awaiting));
+    fn highlighted_channel(&self) -> Option<(&List, &Channel)> {
+        let list = self.lists.get(self.open_list?)?;
+
+        list.channels
+            .get(self.channel_highlight)
+            .map(|channel| (list, channel))
+    }
+
+    fn remember_quality(&mut self, chosen: &Channel, tx: &UnboundedSender<Event>) {
+        let index = match self.open_list {
+            Some(index) => index,
+            None => return,
+        };
+
+        match self.lists.get_mut(index).and_then(|list| {
+            list.channels
+                .iter_mut()
+                .find(|channel| channel.handle == chosen.handle)
+        }) {
+            Some(channel) if channel.quality != chosen.quality => {
+                channel.quality = chosen.quality.clone();
+            }
+            _ => return,
+        }
+
+        self.save_list(index, tx);
+    }
+
+    fn save_list(&self, index: usize, tx: &UnboundedSender<Event>) {
+        if let Some(list) = self.lists.get(index) {
+            match list.save_to_file(&list.file()) {
+                Ok(()) => {
+                    let _result = tx.send(Event::ChannelsSaved);
                 }
+                Err(e) => error!("Could not save list {}: {e}", list.name),
+            }
+        }
+    }
+
+    fn check_name(&self, name: &str, renaming: Option<usize>) -> Result<(), String> {
+        if name.is_empty() {
+            return Err(String::from("A list needs a name"));
+        }
+
+        if name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(format!("{name} can't be used as a list name"));
+        }
+
+        let taken =
+            self.lists.iter().enumerate().any(|(index, list)| {
+                Some(index) != renaming && list.name.eq_ignore_ascii_case(name)
+            });
+
+        if taken {
+            return Err(format!("There is already a list called {name}"));
+        }
+
+        Ok(())
+    }
+
+    // Keeps the same lists open and highlighted, lists are ordered by file name
+    fn sort_lists(&mut self, highlighted: &str) {
+        let open = self
+            .open_list
+            .and_then(|index| self.lists.get(index))
+            .map(|list| list.path.clone());
+
+        self.lists.sort_by(|a, b| a.path.cmp(&b.path));
+
+        self.open_list = open.and_then(|path| self.lists.iter().position(|list| list.path == path));
+        self.highlight = self
+            .lists
+            .iter()
+            .position(|list| list.path == highlighted)
+            .unwrap_or(0);
+    }
 
-                let name = file_name.unwrap_or_default();
+    fn create_list(&mut self, name: &str, tx: &UnboundedSender<Event>) {
+        if let Err(e) = self.check_name(name, None) {
+            notify(tx, Severity::Error, "Could Not Create List", &e);
+            return;
+        }
+
+        let list = List::new(name.to_owned(), format!("{name}{LIST_FILE_EXTENSION}"));
+
+        if let Err(e) = create_dir_all(List::dir())
+            .map_err(anyhow::Error::from)
+            .and_then(|_| list.save_to_file(&list.file()))
+        {
+            notify(
+                tx,
+                Severity::Error,
+                "Could Not Create List",
+                &format!("{e:#}"),
+            );
+            return;
+        }
+
+        let path = list.path.clone();
+        self.lists.push(list);
+        self.sort_lists(&path);
+    }
+
+    fn rename_list(&mut self, index: usize, name: &str, tx: &UnboundedSender<Event>) {
+        if let Err(e) = self.check_name(name, Some(index)) {
+            notify(tx, Severity::Error, "Could Not Rename List", &e);
+            return;
+        }
+
+        if let Some(list) = self.lists.get_mut(index) {
+            let path = format!("{name}{LIST_FILE_EXTENSION}");
+            let target = List::dir().join(&path);
+
+            // Another list's file, maybe one not loaded, unless only the case changed
+            if !path.eq_ignore_ascii_case(&list.path) && storage::exists(&target) {
+                notify(
+                    tx,
+                    Severity::Error,
+                    "Could Not Rename List",
+                    &format!("There already is a list file called {path}"),
+                );
+                return;
+            }
+
+            if let Err(e) = rename(list.file(), target) {
+                notify(tx, Severity::Error, "Could Not Rename List", &e.to_string());
+                return;
+            }
+
+            list.name = name.to_owned();
+            list.path = path.clone();
 
-                List {
-                    channels,
-                    name: name.replace(LISTS_FILE_EXTENSION, ""),
-                    path: name,
+            self.sort_lists(&path);
+        }
+    }
+
+    fn delete_list(&mut self, index: usize, tx: &UnboundedSender<Event>) {
+        if let Some(list) = self.lists.get(index) {
+            // A JSON file left from before lists were TOML would otherwise bring it back
+            if let Err(e) = storage::remove(&list.file()) {
+                notify(tx, Severity::Error, "Could Not Delete List", &e.to_string());
+                return;
+            }
+
+            self.lists.remove(index);
+
+            let _result = tx.send(Event::ChannelsSaved);
+
+            self.open_list = match self.open_list {
+                Some(open) if open == index => {
+                    self.focused_panel = ListsPanel::Lists;
+                    None
                 }
-            })
-            .collect::<Vec<List>>();
+                Some(open) if open > index => Some(open - 1),
+                open => open,
+            };
+            self.highlight = self.highlight.min(self.lists.len().saturating_sub(1));
+        }
+    }
 
-        Self::new(0, &lists, None, 0)
+    fn add_channel(&mut self, handle: &str, tx: &UnboundedSender<Event>) {
+        if let Some(index) = self.open_list {
+            let channel = Channel::new(handle.to_owned(), handle.to_owned());
+
+            if self.insert_channel(index, channel.clone(), tx) {
+                let _result = tx.send(Event::CheckChannels(vec![channel]));
+            }
+        }
     }
 
-    pub fn channel_check(&mut self) {
-        if let Some(open_list_index) = self.open_list {
-            if let Some(list) = self.lists.get(open_list_index) {
-                let mut channels = list.channels.clone();
+    // Whether the channel was added, it isn't if the list already has it
+    fn insert_channel(
+        &mut self,
+        index: usize,
+        channel: Channel,
+        tx: &UnboundedSender<Event>,
+    ) -> bool {
+        if let Some(list) = self.lists.get_mut(index) {
+            if list
+                .channels
+                .iter()
+                .any(|existing| existing.handle.eq_ignore_ascii_case(&channel.handle))
+            {
+                notify(
+                    tx,
+                    Severity::Warning,
+                    "Already Added",
+                    &format!("{} is already in {}", channel.friendly_name, list.name),
+                );
+
+                return false;
+            }
 
-                while let Ok((handle, (status, game_name))) = self.channel_check.try_recv() {
-                    if let Some(index) = channels
-                        .iter()
-                        .position(|channel| channel.handle == handle && channel.status != status)
-                    {
-                        channels[index].status = status;
-                        channels[index].game = game_name;
-                    }
+            list.channels.push(channel);
+            self.save_list(index, tx);
+
+            return true;
+        }
+
+        false
+    }
+
+    fn remove_channel(&mut self, tx: &UnboundedSender<Event>) {
+        if let Some(index) = self.open_list {
+            if let Some(list) = self.lists.get_mut(index) {
+                if self.channel_highlight < list.channels.len() {
+                    list.channels.remove(self.channel_highlight);
+                    self.channel_highlight = self
+                        .channel_highlight
+                        .min(list.channels.len().saturating_sub(1));
+                    self.save_list(index, tx);
                 }
+            }
+        }
+    }
 
-                self.lists[open_list_index].channels = channels;
+    fn transfer_channel(&mut self, choice: usize, tx: &UnboundedSender<Event>) {
+        let transfer = match self.transfer.take() {
+            Some(transfer) => transfer,
+            None => return,
+        };
+
+        let (target, channel) = match (transfer.targets.get(choice), self.highlighted_channel()) {
+            (Some(target), Some((_, channel))) => (*target, channel.clone()),
+            _ => return,
+        };
+
+        if self.insert_channel(target, channel, tx) && transfer.moving {
+            self.remove_channel(tx);
+        }
+    }
+
+    pub fn channel_check(&mut self) {
+        while let Ok(update) = self.channel_check.try_recv() {
+            // The same channel can be in more than one list
+            for channel in self
+                .lists
+                .iter_mut()
+                .flat_map(|list| list.channels.iter_mut())
+            {
+                channel.apply(&update);
             }
+
+            self.refresh.updated();
         }
     }
 }
@@ -162,32 +426,63 @@ impl Lists {
 #[async_trait]
 impl State for Lists {
     #[allow(clippy::ignored_unit_patterns)]
-    async fn tick(&self, _: &Option<Account>, _: u64, _: UnboundedSender<Event>) {}
+    async fn tick(&self, _: &Option<Account>, _: u64, tx: UnboundedSender<Event>) {
+        if self.refresh.due() {
+            let _result = tx.send(Event::RefreshChannels);
+        }
+    }
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, _: u64) {
-        render::lists(
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, _: u64) {
+        self.hits = render::lists(
             theme,
             frame,
             self.highlight,
             &self.lists,
+            &self.columns,
             &self.input_handler.render(),
             self.focused_panel,
             self.open_list,
             self.channel_highlight,
+            self.refresh.since_update(),
         );
     }
 
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         account: &Option<Account>,
+        statuses: &StatusCache,
         tx: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match event {
             Event::Exited => Some(Transition::To(AppState::Exit(Exit::new()))),
             Event::CheckChannels(channels) => {
                 if let Some(acc) = account {
-                    Channel::check(&channels, acc, &self.channel_check_sender);
+                    Channel::check(&channels, acc, statuses, &self.channel_check_sender);
+                }
+
+                None
+            }
+            Event::StatusChanged(update) => {
+                let _result = self.channel_check_sender.send(update);
+
+                None
+            }
+            Event::RefreshChannels => {
+                // Several of these can be queued before the first one is handled
+                if !self.refresh.due() {
+                    return None;
+                }
+
+                self.refresh.schedule();
+
+                // Only the open list is on screen
+                let open_list = self.open_list.and_then(|index| self.lists.get(index));
+
+                if let (Some(acc), Some(list)) = (account, open_list) {
+                    if !list.channels.is_empty() {
+                        Channel::check(&list.channels, acc, statuses, &self.channel_check_sender);
+                    }
                 }
 
                 None
@@ -205,59 +500,144 @@ impl State for Lists {
                     title, message, duration, callback,
                 ))))
             }
-            Event::ChatChoice(choice) => {
-                let chat_choice = choice == 1;
+            Event::PlayerChoice(choice) => {
+                if let Some((list, channel)) = self.highlighted_channel() {
+                    self.pending_launch = launch::player_chosen(
+                        channel,
+                        preferred_player(list, channel),
+                        &self.players,
+                        choice,
+                        &tx,
+                    );
+                }
 
-                if let Some(list_index) = self.open_list {
-                    if let Some(list) = self.lists.get(list_index) {
-                        if let Some(channel) = list.channels.get(self.channel_highlight) {
-                            let _result =
-                                tx.send(Event::ChannelSelected((*channel).clone(), chat_choice));
-                        }
-                    }
+                None
+            }
+            Event::EntryAdded(name) => {
+                let name = name.trim();
+
+                match self.focused_panel {
+                    ListsPanel::Lists => self.create_list(name, &tx),
+                    ListsPanel::ListContent if !name.is_empty() => self.add_channel(name, &tx),
+                    ListsPanel::ListContent => {}
                 }
 
                 None
             }
-            Event::ChannelSelected(channel, chat) => {
-                if let Err(e) = channel.launch() {
-                    eprintln!("Error opening stream: {e}");
+            Event::RemoveConfirmed(0) => {
+                match self.focused_panel {
+                    ListsPanel::Lists => self.delete_list(self.highlight, &tx),
+                    ListsPanel::ListContent => self.remove_channel(&tx),
                 }
 
-                if chat {
-                    if let Err(e) = channel.launch_chat() {
-                        eprintln!("Error opening chat: {e}");
+                None
+            }
+            Event::EntryRenamed(name) => {
+                let name = name.trim();
+
+                match self.focused_panel {
+                    ListsPanel::Lists => self.rename_list(self.highlight, name, &tx),
+                    ListsPanel::ListContent => {
+                        if let Some(index) = self.open_list {
+                            if let Some(channel) = self
+                                .lists
+                                .get_mut(index)
+                                .and_then(|list| list.channels.get_mut(self.channel_highlight))
+                            {
+                                if !name.is_empty() {
+                                    channel.friendly_name = name.to_owned();
+                                    self.save_list(index, &tx);
+                                }
+                            }
+                        }
                     }
                 }
 
                 None
             }
-            Event::CycleTab(direction) => match direction {
-                MoveDirection::Left | MoveDirection::Right => {
-                    // TODO shouldn't be reloading this every time
-                    let channels = Channel::load_from_file(CHANNELS_FILE)
-                        .map_or_else(|_| Vec::new(), |channels| channels);
+            Event::ListChoice(choice) => {
+                self.transfer_channel(choice, &tx);
 
-                    Some(Transition::To(AppState::Home(Home::init(
-                        channels.as_slice(),
-                        &tx,
-                    ))))
+                None
+            }
+            Event::QualitiesFetched(id, qualities) => {
+                launch::qualities_fetched(&mut self.pending_launch, id, qualities, &tx);
+
+                None
+            }
+            Event::QualityChoice(choice) => {
+                if let Some(channel) = launch::quality_chosen(&mut self.pending_launch, choice, &tx)
+                {
+                    self.remember_quality(&channel, &tx);
                 }
-                _ => None,
-            },
+
+                None
+            }
+            Event::ChannelSelected(channel, player, chat) => {
+                launch::open(&channel, &player, chat, &tx);
+
+                None
+            }
+            Event::SelectTab(tab) => open_tab(tab, LISTS_TAB, statuses, &tx),
+            Event::CycleTab(MoveDirection::Right) => {
+                Some(Transition::To(AppState::Follows(Follows::init(&tx))))
+            }
+            Event::CycleTab(MoveDirection::Left) => {
+                // TODO shouldn't be reloading this every time
+                let channels = Channel::load_from_file(&Channel::favourites_file())
+                    .unwrap_or_else(|_| Vec::new());
+
+                Some(Transition::To(AppState::Home(Home::init(
+                    channels.as_slice(),
+                    statuses,
+                    &tx,
+                ))))
+            }
             _ => None,
         }
     }
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event> {
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
         self.input_handler.handle(key_event)
     }
 
+    fn flush(&mut self) -> Vec<Event> {
+        self.input_handler.flush()
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        match gesture(&mut self.mouse, &self.hits, mouse_event) {
+            Some(Gesture::Tab(tab)) => vec![Event::SelectTab(tab)],
+            Some(Gesture::Focus(panel)) => {
+                self.focused_panel = panel;
+
+                Vec::new()
+            }
+            Some(Gesture::Highlight(panel, index)) => {
+                self.highlight(panel, index);
+
+                Vec::new()
+            }
+            Some(Gesture::Open(panel, index)) => {
+                self.highlight(panel, index);
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Scroll(panel, direction)) => {
+                self.focused_panel = panel;
+
+                vec![Event::CycleHighlight(direction)]
+            }
+            Some(Gesture::Choose(_)) | None => Vec::new(),
+        }
+    }
+
     fn process(&mut self, action: Event, tx: &UnboundedSender<Event>) {
         match action {
-            Event::Exited | Event::CycleTab(_) => {
+            Event::Exited | Event::CycleTab(_) | Event::SelectTab(_) | Event::ShowNotifications => {
                 let _result = tx.send(action);
             }
+            Event::PickTheme => theme_popup(tx),
             Event::CycleHighlight(direction) => match self.focused_panel {
                 ListsPanel::Lists => {
                     self.highlight = match direction {
@@ -285,16 +665,127 @@ impl State for Lists {
             Event::HomeEndHighlight(end) => {
                 self.highlight = match end {
                     MoveEnd::First => 0,
-                    MoveEnd::Last => self.lists.len() - 1,
+                    MoveEnd::Last => self.lists.len().saturating_sub(1),
                 };
             }
+            Event::AddEntry => match self.focused_panel {
+                ListsPanel::Lists => add_popup(tx, "New List", "What should the list be called?"),
+                ListsPanel::ListContent => {
+                    if let Some(list) = self.open_list.and_then(|index| self.lists.get(index)) {
+                        add_popup(
+                            tx,
+                            "Add Channel",
+                            &format!(
+                                "Which channel do you want to add to {}? Enter its handle",
+                                list.name
+                            ),
+                        );
+                    }
+                }
+            },
+            Event::RemoveEntry => match self.focused_panel {
+                ListsPanel::Lists => {
+                    if let Some(list) = self.lists.get(self.highlight) {
+                        delete_popup(tx, &list.name);
+                    }
+                }
+                ListsPanel::ListContent => {
+                    if let Some((list, channel)) = self.highlighted_channel() {
+                        remove_popup(tx, &channel.friendly_name, &list.name);
+                    }
+                }
+            },
+            Event::MoveEntry(direction) if self.focused_panel == ListsPanel::ListContent => {
+                if let Some(index) = self.open_list {
+                    if let Some(list) = self.lists.get_mut(index) {
+                        let target = match direction {
+                            MoveDirection::Up => self.channel_highlight.checked_sub(1),
+                            MoveDirection::Down => Some(self.channel_highlight + 1),
+                            _ => None,
+                        };
+
+                        if let Some(target) = target.filter(|target| *target < list.channels.len())
+                        {
+                            list.channels.swap(self.channel_highlight, target);
+                            self.channel_highlight = target;
+                            self.save_list(index, tx);
+                        }
+                    }
+                }
+            }
+            Event::RenameEntry => match self.focused_panel {
+                ListsPanel::Lists => {
+                    if let Some(list) = self.lists.get(self.highlight) {
+                        rename_popup(tx, &list.name);
+                    }
+                }
+                ListsPanel::ListContent => {
+                    if let Some((_, channel)) = self.highlighted_channel() {
+                        rename_popup(tx, &channel.friendly_name);
+                    }
+                }
+            },
+            Event::ToggleNotify if self.focused_panel == ListsPanel::ListContent => {
+                if let Some(index) = self.open_list {
+                    if let Some(channel) = self
+                        .lists
+                        .get_mut(index)
+                        .and_then(|list| list.channels.get_mut(self.channel_highlight))
+                    {
+                        channel.notify = !channel.notify;
+
+                        notify_toggled(tx, channel);
+                        self.save_list(index, tx);
+                    }
+                }
+            }
+            Event::CopyChannel | Event::MoveChannel
+                if self.focused_panel == ListsPanel::ListContent =>
+            {
+                if let Some((list, channel)) = self.highlighted_channel() {
+                    let moving = matches!(action, Event::MoveChannel);
+
+                    let (targets, names): (Vec<usize>, Vec<String>) = self
+                        .lists
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| Some(*index) != self.open_list)
+                        .map(|(index, target)| (index, target.name.clone()))
+                        .unzip();
+
+                    if targets.is_empty() {
+                        notify(
+                            tx,
+                            Severity::Info,
+                            "No Other Lists",
+                            "Create another list to send channels to",
+                        );
+                        return;
+                    }
+
+                    list_popup(
+                        tx,
+                        format!(
+                            "Which list should {} be {} to from {}?",
+                            channel.friendly_name,
+                            if moving { "moved" } else { "copied" },
+                            list.name
+                        ),
+                        &names,
+                    );
+
+                    self.transfer = Some(Transfer { moving, targets });
+                }
+            }
             Event::Selected => match self.focused_panel {
                 ListsPanel::Lists => {
                     self.open_list = Some(self.highlight);
                     self.channel_highlight = 0;
                 }
                 ListsPanel::ListContent => {
-                    chat_popup(tx);
+                    if let Some((list, channel)) = self.highlighted_channel() {
+                        player_popup(tx, &self.players.choices(preferred_player(list, channel)));
+                    }
                 }
             },
             Event::CyclePanel(direction) => {
@@ -311,3 +802,7 @@ impl State for Lists {
         }
     }
 }
+
+fn preferred_player<'a>(list: &'a List, channel: &'a Channel) -> Option<&'a str> {
+    channel.player.as_deref().or(list.player.as_deref())
+}
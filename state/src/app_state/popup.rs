@@ -6,20 +6,21 @@ use crate::{
     app_state::exit::Exit,
     event::Event,
     input_mappings::{choice_inputs, timed_info_inputs, typing_inputs, user_input_inputs},
+    mouse::{gesture, Gesture},
     state::{AppState, MoveDirection, MoveEnd, State},
     transition::Transition,
     util::{index_add, index_subtract},
 };
 use async_trait::async_trait;
 use choice::Choice;
-use crossterm::event::{KeyCode, KeyEvent};
-use input::handler::Handler;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use input::{handler::Handler, mouse::MouseHandler};
 use std::fmt::{Display, Formatter, Result};
 use timed_info::TimedInfo;
 use tokio::sync::mpsc::UnboundedSender;
 use tui::{backend::Backend, terminal::Frame};
-use twitch::account::Account;
-use ui::{render, theme::Theme};
+use twitch::{account::Account, player::Player, status_cache::StatusCache};
+use ui::{hit::Hits, render, theme::Theme};
 use user_input::Input;
 
 pub type Callback = fn(&UnboundedSender<Event>, &Output);
@@ -43,7 +44,8 @@ impl Display for Type {
 #[derive(Clone)]
 pub enum Output {
     Input(String),
-    Index(usize),
+    /// The option picked and where it was in the list.
+    Index(usize, String),
 }
 
 pub struct Popup {
@@ -52,6 +54,8 @@ pub struct Popup {
     pub variant: Type,
     callback: Option<Callback>,
     input_handler: Handler<Event>,
+    hits: Hits<()>,
+    mouse: MouseHandler,
 }
 
 impl Popup {
@@ -100,16 +104,16 @@ impl Popup {
 }
 
 fn new(title: String, message: String, variant: Type, callback: Option<Callback>) -> Popup {
-    let inputs = match &variant {
-        Type::Choice(_) => choice_inputs(),
+    let input_handler = match &variant {
+        Type::Choice(_) => Handler::new(choice_inputs()),
         Type::Input(popup) => {
             if popup.typing {
-                typing_inputs()
+                Handler::without_counts(typing_inputs())
             } else {
-                user_input_inputs()
+                Handler::new(user_input_inputs())
             }
         }
-        Type::TimedInfo(_) => timed_info_inputs(),
+        Type::TimedInfo(_) => Handler::new(timed_info_inputs()),
     };
 
     Popup {
@@ -117,7 +121,9 @@ fn new(title: String, message: String, variant: Type, callback: Option<Callback>
         message,
         variant,
         callback,
-        input_handler: Handler::new(inputs),
+        input_handler,
+        hits: Hits::default(),
+        mouse: MouseHandler::default(),
     }
 }
 
@@ -132,17 +138,21 @@ impl State for Popup {
         }
     }
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+        self.hits = Hits::default();
+
         match &self.variant {
-            Type::Choice(choice) => render::choice(
-                theme,
-                frame,
-                &self.input_handler.render(),
-                &self.title,
-                &self.message,
-                choice.selected,
-                &choice.options,
-            ),
+            Type::Choice(choice) => {
+                self.hits = render::choice(
+                    theme,
+                    frame,
+                    &self.input_handler.render(),
+                    &self.title,
+                    &self.message,
+                    choice.selected,
+                    &choice.options,
+                );
+            }
             Type::Input(input) => render::input(
                 theme,
                 frame,
@@ -165,9 +175,10 @@ impl State for Popup {
     }
 
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         _: &Option<Account>,
+        _: &StatusCache,
         _: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match event {
@@ -177,18 +188,39 @@ impl State for Popup {
         }
     }
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event> {
-        let action = self.input_handler.handle(key_event);
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
+        let actions = self.input_handler.handle(key_event);
 
         if let Type::Input(popup) = &self.variant {
-            if popup.typing && action.is_none() {
+            if popup.typing && actions.is_empty() {
                 if let KeyCode::Char(char) = key_event.code {
-                    return Some(Event::Typed(char));
+                    return vec![Event::Typed(char)];
                 }
             }
         }
 
-        action
+        actions
+    }
+
+    fn flush(&mut self) -> Vec<Event> {
+        self.input_handler.flush()
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        let popup = match &mut self.variant {
+            Type::Choice(popup) => popup,
+            Type::Input(_) | Type::TimedInfo(_) => return Vec::new(),
+        };
+
+        match gesture(&mut self.mouse, &self.hits, mouse_event) {
+            Some(Gesture::Choose(index)) => {
+                popup.selected = index;
+
+                vec![Event::Selected]
+            }
+            Some(Gesture::Scroll((), direction)) => vec![Event::CycleHighlight(direction)],
+            _ => Vec::new(),
+        }
     }
 
     fn process(&mut self, action: Event, tx: &UnboundedSender<Event>) {
@@ -217,12 +249,17 @@ impl State for Popup {
                     let _result = tx.send(Event::PopupEnded);
 
                     if let Some(func) = self.callback {
-                        func(tx, &Output::Index(popup.selected));
+                        let option = popup.options.get(popup.selected).cloned();
+
+                        func(
+                            tx,
+                            &Output::Index(popup.selected, option.unwrap_or_default()),
+                        );
                     }
                 }
                 Type::Input(ref mut popup) => {
                     popup.typing = true;
-                    self.input_handler = Handler::new(typing_inputs());
+                    self.input_handler = Handler::without_counts(typing_inputs());
                 }
                 Type::TimedInfo(_) => {}
             },
@@ -274,15 +311,21 @@ impl State for Popup {
     }
 }
 
-pub fn chat_choice(tx: &UnboundedSender<Event>, output: &Output) {
-    if let Output::Index(choice) = output {
-        let _result = tx.send(Event::ChatChoice(*choice));
+pub fn player_choice(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(choice, _) = output {
+        let _result = tx.send(Event::PlayerChoice(*choice));
     }
 }
 
-pub fn chat_choice_search(tx: &UnboundedSender<Event>, output: &Output) {
-    if let Output::Index(choice) = output {
-        let _result = tx.send(Event::ChatChoiceSearch(*choice));
+pub fn player_choice_search(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(choice, _) = output {
+        let _result = tx.send(Event::PlayerChoiceSearch(*choice));
+    }
+}
+
+pub fn quality_choice(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(choice, _) = output {
+        let _result = tx.send(Event::QualityChoice(*choice));
     }
 }
 
@@ -320,20 +363,134 @@ pub fn redirect_url_port_submit(tx: &UnboundedSender<Event>, output: &Output) {
 }
 
 #[allow(clippy::module_name_repetitions)]
-pub fn chat_popup(tx: &UnboundedSender<Event>) {
+pub fn player_popup(tx: &UnboundedSender<Event>, choices: &[(Player, bool)]) {
+    let _result = tx.send(Event::ChoicePopupStarted((
+        String::from("Launch"),
+        String::from("Which player do you want to open the stream with?"),
+        player_options(choices),
+        Some(player_choice),
+    )));
+}
+
+pub fn player_popup_search(tx: &UnboundedSender<Event>, choices: &[(Player, bool)]) {
+    let _result = tx.send(Event::ChoicePopupStarted((
+        String::from("Launch"),
+        String::from("Which player do you want to open the stream with?"),
+        player_options(choices),
+        Some(player_choice_search),
+    )));
+}
+
+pub fn theme_choice(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(_, name) = output {
+        let _result = tx.send(Event::ThemeChoice(name.clone()));
+    }
+}
+
+pub fn remove_choice(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(choice, _) = output {
+        let _result = tx.send(Event::RemoveConfirmed(*choice));
+    }
+}
+
+pub fn list_choice(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Index(choice, _) = output {
+        let _result = tx.send(Event::ListChoice(*choice));
+    }
+}
+
+pub fn entry_added_submit(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Input(input) = output {
+        let _result = tx.send(Event::EntryAdded(input.clone()));
+    }
+}
+
+pub fn entry_renamed_submit(tx: &UnboundedSender<Event>, output: &Output) {
+    if let Output::Input(input) = output {
+        let _result = tx.send(Event::EntryRenamed(input.clone()));
+    }
+}
+
+/// Asks before removing `name`, answering with `RemoveConfirmed(0)` if it should go.
+pub fn remove_popup(tx: &UnboundedSender<Event>, name: &str, from: &str) {
     let _result = tx.send(Event::ChoicePopupStarted((
-        String::from("Launch Chat"),
-        String::from("Do you want to launch the chat with the stream?"),
-        vec![String::from("No"), String::from("Yes")],
-        Some(chat_choice),
+        String::from("Remove"),
+        format!("Remove {name} from {from}?"),
+        vec![String::from("Remove"), String::from("Cancel")],
+        Some(remove_choice),
     )));
 }
 
-pub fn chat_popup_search(tx: &UnboundedSender<Event>) {
+pub fn delete_popup(tx: &UnboundedSender<Event>, name: &str) {
     let _result = tx.send(Event::ChoicePopupStarted((
-        String::from("Launch Chat"),
-        String::from("Do you want to launch the chat with the stream?"),
-        vec![String::from("No"), String::from("Yes")],
-        Some(chat_choice_search),
+        String::from("Delete"),
+        format!("Delete the list {name} and its file?"),
+        vec![String::from("Delete"), String::from("Cancel")],
+        Some(remove_choice),
     )));
 }
+
+pub fn add_popup(tx: &UnboundedSender<Event>, title: &str, message: &str) {
+    let _result = tx.send(Event::InputPopupStarted((
+        title.to_owned(),
+        message.to_owned(),
+        Some(entry_added_submit),
+    )));
+}
+
+pub fn rename_popup(tx: &UnboundedSender<Event>, name: &str) {
+    let _result = tx.send(Event::InputPopupStarted((
+        String::from("Rename"),
+        format!("What should {name} be called?"),
+        Some(entry_renamed_submit),
+    )));
+}
+
+pub fn list_popup(tx: &UnboundedSender<Event>, message: String, lists: &[String]) {
+    let _result = tx.send(Event::ChoicePopupStarted((
+        String::from("Pick a List"),
+        message,
+        lists.to_vec(),
+        Some(list_choice),
+    )));
+}
+
+pub fn quality_popup(tx: &UnboundedSender<Event>, channel_name: &str, qualities: &[String]) {
+    let _result = tx.send(Event::ChoicePopupStarted((
+        String::from("Quality"),
+        format!("Which quality do you want to watch {channel_name} in?"),
+        qualities.to_vec(),
+        Some(quality_choice),
+    )));
+}
+
+/// Offers every theme there is, saying which one is in use.
+pub fn theme_popup(tx: &UnboundedSender<Event>) {
+    let current = Theme::load().name;
+
+    // Options are left as just the names, which is what the choice is sent back as
+    let options = Theme::available()
+        .into_iter()
+        .map(|theme| theme.name)
+        .collect();
+
+    let _result = tx.send(Event::ChoicePopupStarted((
+        String::from("Theme"),
+        format!("Which theme do you want to use? {current} is in use"),
+        options,
+        Some(theme_choice),
+    )));
+}
+
+fn player_options(choices: &[(Player, bool)]) -> Vec<String> {
+    choices
+        .iter()
+        .map(|(player, chat)| {
+            if *chat {
+                format!("{} + chat", player.name)
+            } else {
+                player.name.clone()
+            }
+        })
+        .collect()
+}
@@ -15,14 +15,14 @@ use async_trait::async_trait;
 use crossterm::event::KeyEvent;
 use input::handler::Handler;
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
 use tui::{backend::Backend, terminal::Frame};
-use twitch::{account::Account, channel::Channel};
+use twitch::{account::Account, channel::Channel, status_cache::StatusCache};
 use ui::{
     render::startup::{account_missing, starting},
     theme::Theme,
 };
 
-const CHANNELS_FILE: &str = "favourites.json";
 const STARTUP_DURATION: u64 = 2;
 
 pub struct Startup {
@@ -56,23 +56,25 @@ impl State for Startup {
         }
     }
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
         starting(theme, frame, timer);
     }
 
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         _: &Option<Account>,
+        statuses: &StatusCache,
         tx: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match event {
             Event::Started => {
-                let channels = Channel::load_from_file(CHANNELS_FILE)
-                    .map_or_else(|_| Vec::new(), |channels| channels);
+                let channels = Channel::load_from_file(&Channel::favourites_file())
+                    .unwrap_or_else(|_| Vec::new());
 
                 Some(Transition::To(AppState::Home(Home::init(
                     channels.as_slice(),
+                    statuses,
                     &tx,
                 ))))
             }
@@ -81,7 +83,7 @@ impl State for Startup {
         }
     }
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event> {
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
         self.input_handler.handle(key_event)
     }
 
@@ -182,31 +184,33 @@ impl State for AccountMissing {
                     let _result = events.send(Event::AccountConfigured(account));
                 }
                 Err(e) => {
-                    eprintln!("Could not set new account: {e}");
+                    error!("Could not set new account: {e:#}");
                     let _result = events.send(Event::Exited);
                 }
             }
         }
     }
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
         account_missing(theme, frame, timer);
     }
 
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         _: &Option<Account>,
+        statuses: &StatusCache,
         tx: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match event {
             Event::Exited => Some(Transition::To(AppState::Exit(Exit::new()))),
             Event::AccountConfigured(_) => {
-                let channels = Channel::load_from_file(CHANNELS_FILE)
-                    .map_or_else(|_| Vec::new(), |channels| channels);
+                let channels = Channel::load_from_file(&Channel::favourites_file())
+                    .unwrap_or_else(|_| Vec::new());
 
                 Some(Transition::To(AppState::Home(Home::init(
                     channels.as_slice(),
+                    statuses,
                     &tx,
                 ))))
             }
@@ -262,7 +266,7 @@ impl State for AccountMissing {
         }
     }
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event> {
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
         self.input_handler.handle(key_event)
     }
 
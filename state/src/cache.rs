@@ -1,5 +1,7 @@
 use crate::{
     app_state::{
+        browse::Browse,
+        follows::Follows,
         home::Home,
         lists::Lists,
         startup::{AccountMissing, Startup},
@@ -9,13 +11,13 @@ use crate::{
 };
 use std::mem::discriminant;
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
 
 #[derive(Default)]
 pub struct Cache {
     storage: Vec<AppState>,
 }
 
-// TODO turn into channel status cache
 // TODO could be more useful, only used for popups
 
 impl Cache {
@@ -38,7 +40,7 @@ impl Cache {
                 return index;
             }
 
-            eprintln!("State found in cache but could not be grabbed");
+            warn!("State found in cache but could not be grabbed");
         }
 
         self.storage.push(state);
@@ -60,6 +62,10 @@ impl Cache {
                 }
                 AppState::Home(s) => return Some(AppState::Home(Home::from_existing(s, tx))),
                 AppState::Lists(s) => return Some(AppState::Lists(Lists::from_existing(s))),
+                AppState::Follows(s) => {
+                    return Some(AppState::Follows(Follows::from_existing(s)));
+                }
+                AppState::Browse(s) => return Some(AppState::Browse(Browse::from_existing(s))),
                 AppState::Popup(_) | AppState::Exit(_) => return None, // Not cached
             }
         }
@@ -4,21 +4,47 @@ use crate::{
 };
 use input::handler::Action;
 use std::fmt::{Display, Formatter, Result};
-use twitch::{account::Account, channel::Channel};
+use twitch::{account::Account, channel::Channel, player::Player, status_service::StatusUpdate};
+use ui::toast::Severity;
 
 #[derive(Clone)]
 pub enum Event {
     Started,
     Exited,
     CheckChannels(Vec<Channel>),
-    ChannelSelected(Channel, bool),
+    RefreshChannels,
+    StatusChanged(StatusUpdate),
+    ChannelsSaved,
+    CheckFollows,
+    SearchChannels,
+    LoadCategories,
+    LoadStreams,
+    ChannelSelected(Channel, Player, bool),
     ChoicePopupStarted((String, String, Vec<String>, Option<Callback>)),
     InputPopupStarted((String, String, Option<Callback>)),
     TimedInfoPopupStarted((String, String, u64, Option<Callback>)),
     PopupEnded,
-    ChatChoice(usize),
-    ChatChoiceSearch(usize),
+    Notify(Severity, String, String),
+    ShowNotifications,
+    AddEntry,
+    EntryAdded(String),
+    RemoveEntry,
+    RemoveConfirmed(usize),
+    MoveEntry(MoveDirection),
+    RenameEntry,
+    ToggleNotify,
+    EntryRenamed(String),
+    CopyChannel,
+    MoveChannel,
+    ListChoice(usize),
+    PlayerChoice(usize),
+    PlayerChoiceSearch(usize),
+    QualitiesFetched(u64, Vec<String>),
+    QualityChoice(usize),
+    PickTheme,
+    ThemeChoice(String),
     CycleTab(MoveDirection),
+    SelectTab(usize),
     CycleHighlight(MoveDirection),
     HomeEndHighlight(MoveEnd),
     Selected,
@@ -42,19 +68,47 @@ impl Display for Event {
             Self::Started => write!(f, "Started"),
             Self::Exited => write!(f, "Exit"),
             Self::CheckChannels(_) => write!(f, "Check Channels"),
-            Self::ChannelSelected(channel, choice) => write!(
+            Self::RefreshChannels => write!(f, "Refresh Channels"),
+            Self::StatusChanged((handle, _)) => write!(f, "Status Changed: {handle}"),
+            Self::ChannelsSaved => write!(f, "Channels Saved"),
+            Self::CheckFollows => write!(f, "Check Follows"),
+            Self::SearchChannels => write!(f, "Search Channels"),
+            Self::LoadCategories => write!(f, "Load Categories"),
+            Self::LoadStreams => write!(f, "Load Streams"),
+            Self::ChannelSelected(channel, player, choice) => write!(
                 f,
-                "Channel {} selected {} chat",
+                "Channel {} selected on {} {} chat",
                 channel.handle,
+                player.name,
                 if *choice { "with" } else { "without" }
             ),
             Self::ChoicePopupStarted(_) => write!(f, "Choice Popup started"),
             Self::InputPopupStarted(_) => write!(f, "Input Popup started"),
             Self::TimedInfoPopupStarted(_) => write!(f, "Timed Info Popup started"),
             Self::PopupEnded => write!(f, "Popup End"),
-            Self::ChatChoice(choice) => write!(f, "Chat Choice: {choice}"),
-            Self::ChatChoiceSearch(choice) => write!(f, "Chat Choice from Search: {choice}"),
+            Self::Notify(severity, title, message) => write!(f, "{severity}: {title} - {message}"),
+            Self::ShowNotifications => write!(f, "Show Notifications"),
+            Self::AddEntry => write!(f, "Add Entry"),
+            Self::EntryAdded(name) => write!(f, "Entry Added: {name}"),
+            Self::RemoveEntry => write!(f, "Remove Entry"),
+            Self::RemoveConfirmed(choice) => write!(f, "Remove Confirmed: {choice}"),
+            Self::MoveEntry(direction) => write!(f, "Move Entry {direction}"),
+            Self::RenameEntry => write!(f, "Rename Entry"),
+            Self::ToggleNotify => write!(f, "Toggle Notify"),
+            Self::EntryRenamed(name) => write!(f, "Entry Renamed: {name}"),
+            Self::CopyChannel => write!(f, "Copy Channel"),
+            Self::MoveChannel => write!(f, "Move Channel"),
+            Self::ListChoice(choice) => write!(f, "List Choice: {choice}"),
+            Self::PlayerChoice(choice) => write!(f, "Player Choice: {choice}"),
+            Self::PlayerChoiceSearch(choice) => write!(f, "Player Choice from Search: {choice}"),
+            Self::QualitiesFetched(id, qualities) => {
+                write!(f, "Fetched {} qualities for launch {id}", qualities.len())
+            }
+            Self::QualityChoice(choice) => write!(f, "Quality Choice: {choice}"),
+            Self::PickTheme => write!(f, "Pick Theme"),
+            Self::ThemeChoice(name) => write!(f, "Theme Choice: {name}"),
             Self::CycleTab(direction) => write!(f, "Cycle Tab {direction}"),
+            Self::SelectTab(tab) => write!(f, "Select Tab {tab}"),
             Self::CycleHighlight(direction) => write!(f, "Cycle Highlight {direction}"),
             Self::HomeEndHighlight(end) => write!(f, "Highlight to {end}"),
             Self::Selected => write!(f, "Selected current highlight"),
@@ -82,9 +136,54 @@ impl Action for Event {
             Self::CycleHighlight(_) | Self::HomeEndHighlight(_) => Some("Cycle List"),
             Self::Selected => Some("Select"),
             Self::CyclePanel(_) => Some("Cycle Panels"),
+            Self::AddEntry => Some("Add"),
+            Self::RemoveEntry => Some("Remove"),
+            Self::MoveEntry(_) => Some("Move"),
+            Self::RenameEntry => Some("Rename"),
+            Self::ToggleNotify => Some("Notify When Live"),
+            Self::CopyChannel => Some("Copy to List"),
+            Self::MoveChannel => Some("Move to List"),
+            Self::PickTheme => Some("Themes"),
+            Self::ShowNotifications => Some("Notifications"),
             Self::StopTyping => Some("Stop Typing"),
             Self::Submit => Some("Submit"),
             _ => None,
         }
     }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Self::Exited => Some("exit"),
+            Self::CycleTab(MoveDirection::Right) => Some("next_tab"),
+            Self::CycleTab(MoveDirection::Left) => Some("previous_tab"),
+            Self::CycleHighlight(MoveDirection::Up) => Some("up"),
+            Self::CycleHighlight(MoveDirection::Down) => Some("down"),
+            Self::HomeEndHighlight(MoveEnd::First) => Some("first"),
+            Self::HomeEndHighlight(MoveEnd::Last) => Some("last"),
+            Self::Selected => Some("select"),
+            Self::CyclePanel(MoveDirection::Left) => Some("panel_left"),
+            Self::CyclePanel(MoveDirection::Right) => Some("panel_right"),
+            Self::AddEntry => Some("add"),
+            Self::RemoveEntry => Some("remove"),
+            Self::MoveEntry(MoveDirection::Up) => Some("move_up"),
+            Self::MoveEntry(MoveDirection::Down) => Some("move_down"),
+            Self::RenameEntry => Some("rename"),
+            Self::ToggleNotify => Some("toggle_notify"),
+            Self::CopyChannel => Some("copy_to_list"),
+            Self::MoveChannel => Some("move_to_list"),
+            Self::PickTheme => Some("themes"),
+            Self::ShowNotifications => Some("notifications"),
+            Self::StopTyping => Some("stop_typing"),
+            Self::Submit => Some("submit"),
+            Self::DeleteChar => Some("delete_char"),
+            _ => None,
+        }
+    }
+
+    fn repeatable(&self) -> bool {
+        matches!(
+            self,
+            Self::CycleHighlight(_) | Self::MoveEntry(_) | Self::CyclePanel(_)
+        )
+    }
 }
@@ -1,45 +1,25 @@
 use crate::{
     event::Event,
+    keymap::{Keymap, BROWSE, CHOICE, FOLLOWS, HOME, INPUT, LISTS, TYPING},
     state::{MoveDirection, MoveEnd},
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use input::keybind::KeyBind;
 
-#[allow(clippy::too_many_lines)]
 pub fn home_inputs() -> Vec<KeyBind<Event>> {
-    [
-        exit(),
-        cycle_tabs(),
-        handle_highlights(),
-        select(),
-        cycle_panel(),
-    ]
-    .concat()
+    configured(HOME)
 }
 
 pub fn typing_inputs() -> Vec<KeyBind<Event>> {
-    vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
-            action: Event::StopTyping,
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
-            action: Event::Submit,
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
-            action: Event::DeleteChar,
-        },
-    ]
+    configured(TYPING)
 }
 
 pub fn choice_inputs() -> Vec<KeyBind<Event>> {
-    [handle_highlights(), select()].concat()
+    configured(CHOICE)
 }
 
 pub fn user_input_inputs() -> Vec<KeyBind<Event>> {
-    select()
+    configured(INPUT)
 }
 
 #[inline]
@@ -48,130 +28,285 @@ pub const fn timed_info_inputs() -> Vec<KeyBind<Event>> {
 }
 
 pub fn lists_inputs() -> Vec<KeyBind<Event>> {
-    [
-        exit(),
-        cycle_tabs(),
-        handle_highlights(),
-        select(),
-        cycle_panel(),
+    configured(LISTS)
+}
+
+pub fn follows_inputs() -> Vec<KeyBind<Event>> {
+    configured(FOLLOWS)
+}
+
+pub fn browse_inputs() -> Vec<KeyBind<Event>> {
+    configured(BROWSE)
+}
+
+/// The binds for a context before the keymap file is applied.
+pub fn default_inputs(context: &str) -> Vec<KeyBind<Event>> {
+    match context {
+        HOME => [
+            exit(),
+            cycle_tabs(),
+            handle_highlights(),
+            select(),
+            cycle_panel(),
+            edit_entries(),
+            toggle_notify(),
+            pick_theme(),
+            show_notifications(),
+        ]
+        .concat(),
+        LISTS => [
+            exit(),
+            cycle_tabs(),
+            handle_highlights(),
+            select(),
+            cycle_panel(),
+            edit_entries(),
+            transfer_channels(),
+            toggle_notify(),
+            pick_theme(),
+            show_notifications(),
+        ]
+        .concat(),
+        FOLLOWS => [
+            exit(),
+            cycle_tabs(),
+            handle_highlights(),
+            select(),
+            pick_theme(),
+            show_notifications(),
+        ]
+        .concat(),
+        BROWSE => [
+            exit(),
+            cycle_tabs(),
+            handle_highlights(),
+            select(),
+            cycle_panel(),
+            pick_theme(),
+            show_notifications(),
+        ]
+        .concat(),
+        TYPING => typing(),
+        CHOICE => [handle_highlights(), select()].concat(),
+        INPUT => select(),
+        _ => Vec::new(),
+    }
+}
+
+fn configured(context: &str) -> Vec<KeyBind<Event>> {
+    let defaults = default_inputs(context);
+
+    match Keymap::installed() {
+        Some(keymap) => keymap.binds(context, defaults),
+        None => defaults,
+    }
+}
+
+fn typing() -> Vec<KeyBind<Event>> {
+    vec![
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            Event::StopTyping,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            Event::Submit,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+            Event::DeleteChar,
+        ),
     ]
-    .concat()
 }
 
 fn exit() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
-            action: Event::Exited,
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
-            action: Event::Exited,
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT),
-            action: Event::Exited,
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            Event::Exited,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
+            Event::Exited,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT),
+            Event::Exited,
+        ),
     ]
 }
 
 fn cycle_tabs() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
-            action: Event::CycleTab(MoveDirection::Right),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
-            action: Event::CycleTab(MoveDirection::Left),
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+            Event::CycleTab(MoveDirection::Right),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            Event::CycleTab(MoveDirection::Left),
+        ),
     ]
 }
 
 fn handle_highlights() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
-            action: Event::CycleHighlight(MoveDirection::Up),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('W'), KeyModifiers::SHIFT),
-            action: Event::CycleHighlight(MoveDirection::Up),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Up, KeyModifiers::NONE),
-            action: Event::CycleHighlight(MoveDirection::Up),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE),
-            action: Event::CycleHighlight(MoveDirection::Down),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
-            action: Event::CycleHighlight(MoveDirection::Down),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
-            action: Event::CycleHighlight(MoveDirection::Down),
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+            Event::CycleHighlight(MoveDirection::Up),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('W'), KeyModifiers::SHIFT),
+            Event::CycleHighlight(MoveDirection::Up),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Up, KeyModifiers::NONE),
+            Event::CycleHighlight(MoveDirection::Up),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE),
+            Event::CycleHighlight(MoveDirection::Down),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
+            Event::CycleHighlight(MoveDirection::Down),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
+            Event::CycleHighlight(MoveDirection::Down),
+        ),
         // First/Last Highlight
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
-            action: Event::HomeEndHighlight(MoveEnd::First),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Up, KeyModifiers::CONTROL),
-            action: Event::HomeEndHighlight(MoveEnd::First),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
-            action: Event::HomeEndHighlight(MoveEnd::Last),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Down, KeyModifiers::CONTROL),
-            action: Event::HomeEndHighlight(MoveEnd::Last),
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            Event::HomeEndHighlight(MoveEnd::First),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Up, KeyModifiers::CONTROL),
+            Event::HomeEndHighlight(MoveEnd::First),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            Event::HomeEndHighlight(MoveEnd::Last),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Down, KeyModifiers::CONTROL),
+            Event::HomeEndHighlight(MoveEnd::Last),
+        ),
+        KeyBind::sequence(
+            &[
+                KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+            ],
+            Event::HomeEndHighlight(MoveEnd::First),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Event::HomeEndHighlight(MoveEnd::Last),
+        ),
     ]
 }
 
 fn select() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
-            action: Event::Selected,
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE),
-            action: Event::Selected,
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            Event::Selected,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE),
+            Event::Selected,
+        ),
     ]
 }
 
+fn edit_entries() -> Vec<KeyBind<Event>> {
+    vec![
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            Event::AddEntry,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE),
+            Event::RemoveEntry,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE),
+            Event::RemoveEntry,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT),
+            Event::MoveEntry(MoveDirection::Up),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT),
+            Event::MoveEntry(MoveDirection::Down),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+            Event::RenameEntry,
+        ),
+    ]
+}
+
+fn transfer_channels() -> Vec<KeyBind<Event>> {
+    vec![
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE),
+            Event::CopyChannel,
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE),
+            Event::MoveChannel,
+        ),
+    ]
+}
+
+fn toggle_notify() -> Vec<KeyBind<Event>> {
+    vec![KeyBind::new(
+        KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE),
+        Event::ToggleNotify,
+    )]
+}
+
 fn cycle_panel() -> Vec<KeyBind<Event>> {
     vec![
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
-            action: Event::CyclePanel(MoveDirection::Left),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
-            action: Event::CyclePanel(MoveDirection::Left),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Left, KeyModifiers::NONE),
-            action: Event::CyclePanel(MoveDirection::Left),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
-            action: Event::CyclePanel(MoveDirection::Right),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT),
-            action: Event::CyclePanel(MoveDirection::Right),
-        },
-        KeyBind {
-            event: KeyEvent::new(KeyCode::Right, KeyModifiers::NONE),
-            action: Event::CyclePanel(MoveDirection::Right),
-        },
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            Event::CyclePanel(MoveDirection::Left),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            Event::CyclePanel(MoveDirection::Left),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Left, KeyModifiers::NONE),
+            Event::CyclePanel(MoveDirection::Left),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+            Event::CyclePanel(MoveDirection::Right),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT),
+            Event::CyclePanel(MoveDirection::Right),
+        ),
+        KeyBind::new(
+            KeyEvent::new(KeyCode::Right, KeyModifiers::NONE),
+            Event::CyclePanel(MoveDirection::Right),
+        ),
     ]
 }
+
+fn pick_theme() -> Vec<KeyBind<Event>> {
+    vec![KeyBind::new(
+        KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE),
+        Event::PickTheme,
+    )]
+}
+
+fn show_notifications() -> Vec<KeyBind<Event>> {
+    vec![KeyBind::new(
+        KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE),
+        Event::ShowNotifications,
+    )]
+}
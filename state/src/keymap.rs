@@ -0,0 +1,145 @@
+use anyhow::Result;
+use input::{
+    handler::Action,
+    keybind::KeyBind,
+    keymap::{self, parse_keys},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+use tokio::sync::OnceCell;
+use twitch::{paths, storage};
+
+use crate::input_mappings::default_inputs;
+
+const KEYMAP_FILE: &str = "keymap.toml";
+
+pub const APP: &str = "app";
+pub const HOME: &str = "home";
+pub const LISTS: &str = "lists";
+pub const FOLLOWS: &str = "follows";
+pub const BROWSE: &str = "browse";
+pub const TYPING: &str = "typing";
+pub const CHOICE: &str = "choice";
+pub const INPUT: &str = "input";
+
+const CONTEXTS: [&str; 8] = [APP, HOME, LISTS, FOLLOWS, BROWSE, TYPING, CHOICE, INPUT];
+
+// Read once at startup and used by every view from then on
+static INSTALLED: OnceCell<Keymap> = OnceCell::const_new();
+
+/// Keys rebound by the user, a section per context mapping key strings to action names.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct Keymap {
+    contexts: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Keymap {
+    #[allow(clippy::missing_errors_doc)]
+    pub fn load_from_file(file: &Path) -> Result<Self> {
+        storage::load(file)
+    }
+
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from_file(&paths::config_file(KEYMAP_FILE)).unwrap_or_default()
+    }
+
+    /// Makes this the keymap views are built with for the rest of the run. Only the first call
+    /// counts.
+    pub fn install(self) {
+        let _result = INSTALLED.set(self);
+    }
+
+    /// The keymap views are built with, if one was installed.
+    #[must_use]
+    pub fn installed() -> Option<&'static Self> {
+        INSTALLED.get()
+    }
+
+    /// `defaults` with whatever was rebound for `context` on top. Anything that can't be used is
+    /// left out, [`check`] is what reports it.
+    #[must_use]
+    pub fn binds<T: Action + Clone>(
+        &self,
+        context: &str,
+        defaults: Vec<KeyBind<T>>,
+    ) -> Vec<KeyBind<T>> {
+        self.rebind(context, defaults).0
+    }
+
+    fn rebind<T: Action + Clone>(
+        &self,
+        context: &str,
+        defaults: Vec<KeyBind<T>>,
+    ) -> (Vec<KeyBind<T>>, Vec<String>) {
+        match self.contexts.get(context) {
+            Some(overrides) => {
+                let (binds, problems) = keymap::rebind(defaults, overrides);
+
+                (
+                    binds,
+                    problems
+                        .into_iter()
+                        .map(|problem| format!("[{context}] {problem}"))
+                        .collect(),
+                )
+            }
+            None => (defaults, Vec::new()),
+        }
+    }
+}
+
+/// Reads the keymap file and installs it, returning everything wrong with it so it can be shown
+/// once at startup. The app's own binds live outside this crate, so they're passed in.
+#[must_use]
+pub fn install_checked<T: Action + Clone>(app_defaults: Vec<KeyBind<T>>) -> Vec<String> {
+    let file = paths::config_file(KEYMAP_FILE);
+
+    if !storage::exists(&file) {
+        Keymap::default().install();
+
+        return Vec::new();
+    }
+
+    let keymap = match Keymap::load_from_file(&file) {
+        Ok(keymap) => keymap,
+        Err(e) => {
+            Keymap::default().install();
+
+            return vec![format!("{e:#}, using the default keys")];
+        }
+    };
+
+    let mut problems: Vec<String> = keymap
+        .contexts
+        .keys()
+        .filter(|context| !CONTEXTS.contains(&context.as_str()))
+        .map(|context| format!("[{context}] is not one of {}", CONTEXTS.join(", ")))
+        .collect();
+
+    let (app_binds, app_problems) = keymap.rebind(APP, app_defaults);
+    problems.extend(app_problems);
+
+    for context in CONTEXTS.into_iter().filter(|context| *context != APP) {
+        problems.extend(keymap.rebind(context, default_inputs(context)).1);
+
+        // The app sees every key before the context does
+        for key in keymap
+            .contexts
+            .get(context)
+            .into_iter()
+            .flat_map(BTreeMap::keys)
+        {
+            if let Ok(keys) = parse_keys(key) {
+                if app_binds.iter().any(|bind| bind.keys == keys) {
+                    problems.push(format!("[{context}] `{key}` is also bound in [{APP}]"));
+                }
+            }
+        }
+    }
+
+    keymap.install();
+
+    problems
+}
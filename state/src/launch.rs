@@ -0,0 +1,162 @@
+use crate::{app_state::popup::quality_popup, event::Event, toast::notify};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::{spawn, sync::mpsc::UnboundedSender};
+use tracing::warn;
+use twitch::{
+    channel::Channel,
+    player::{Player, Players},
+    quality,
+};
+use ui::toast::Severity;
+
+// Tells the qualities looked up for one launch from those of a launch started after it
+static LAUNCHES: AtomicU64 = AtomicU64::new(0);
+
+pub struct PendingLaunch {
+    pub id: u64,
+    pub channel: Channel,
+    pub player: Player,
+    pub chat: bool,
+    pub qualities: Vec<String>,
+}
+
+impl PendingLaunch {
+    /// Launches straight away if the player doesn't care about quality, otherwise starts looking up
+    /// the available qualities and returns the launch waiting on them.
+    pub fn start(
+        channel: Channel,
+        player: Player,
+        chat: bool,
+        tx: &UnboundedSender<Event>,
+    ) -> Option<Self> {
+        if !player.uses_quality() {
+            let _result = tx.send(Event::ChannelSelected(channel, player, chat));
+            return None;
+        }
+
+        let id = LAUNCHES.fetch_add(1, Ordering::Relaxed);
+        let lookup_channel = channel.clone();
+        let lookup_tx = tx.clone();
+
+        spawn(async move {
+            let qualities = quality::available(&lookup_channel)
+                .await
+                .unwrap_or_default();
+
+            let _result = lookup_tx.send(Event::QualitiesFetched(id, qualities));
+        });
+
+        Some(Self {
+            id,
+            channel,
+            player,
+            chat,
+            qualities: Vec::new(),
+        })
+    }
+
+    /// Stores the available qualities, remembered one first. Returns false if there was nothing to
+    /// choose from, in which case the stream is launched with the remembered/default quality.
+    pub fn qualities_fetched(
+        &mut self,
+        qualities: Vec<String>,
+        tx: &UnboundedSender<Event>,
+    ) -> bool {
+        self.qualities = quality::with_preferred(qualities, self.channel.quality.as_deref());
+
+        if self.qualities.is_empty() {
+            let _result = tx.send(Event::ChannelSelected(
+                self.channel.clone(),
+                self.player.clone(),
+                self.chat,
+            ));
+
+            return false;
+        }
+
+        true
+    }
+
+    /// Launches with the chosen quality, returning the channel with it set so it can be remembered.
+    pub fn choose(mut self, choice: usize, tx: &UnboundedSender<Event>) -> Channel {
+        if let Some(quality) = self.qualities.get(choice) {
+            self.channel.quality = Some(quality.clone());
+        }
+
+        let _result = tx.send(Event::ChannelSelected(
+            self.channel.clone(),
+            self.player,
+            self.chat,
+        ));
+
+        self.channel
+    }
+}
+
+/// Opens the channel, and its chat if asked to, saying how it went in a toast. The chat is opened
+/// even if the stream couldn't be, and each failure gets its own toast.
+pub fn open(channel: &Channel, player: &Player, chat: bool, tx: &UnboundedSender<Event>) {
+    match channel.launch(player) {
+        Ok(()) => notify(
+            tx,
+            Severity::Info,
+            "Launched",
+            &format!("{} opened in {}", channel.friendly_name, player.name),
+        ),
+        Err(e) => {
+            warn!("Could not launch {}: {e}", channel.handle);
+
+            notify(tx, Severity::Error, "Launch Failed", &e.to_string());
+        }
+    }
+
+    if chat {
+        if let Err(e) = channel.launch_chat() {
+            warn!("Could not open the chat for {}: {e}", channel.handle);
+
+            notify(tx, Severity::Error, "Chat Failed", &e.to_string());
+        }
+    }
+}
+
+/// Starts launching `channel` with the player picked from its choices, `preferred` first.
+pub fn player_chosen(
+    channel: &Channel,
+    preferred: Option<&str>,
+    players: &Players,
+    choice: usize,
+    tx: &UnboundedSender<Event>,
+) -> Option<PendingLaunch> {
+    let (player, chat) = players.choices(preferred).into_iter().nth(choice)?;
+
+    PendingLaunch::start(channel.clone(), player, chat, tx)
+}
+
+/// Asks which quality to launch the pending launch in, or launches it if there is no choice.
+/// Qualities looked up for an earlier launch are ignored.
+pub fn qualities_fetched(
+    pending_launch: &mut Option<PendingLaunch>,
+    id: u64,
+    qualities: Vec<String>,
+    tx: &UnboundedSender<Event>,
+) {
+    if let Some(pending) = pending_launch.as_mut().filter(|pending| pending.id == id) {
+        if pending.qualities_fetched(qualities, tx) {
+            quality_popup(tx, &pending.channel.friendly_name, &pending.qualities);
+        } else {
+            *pending_launch = None;
+        }
+    }
+}
+
+/// Launches the pending launch in the chosen quality, returning the channel with it set so it can
+/// be remembered.
+pub fn quality_chosen(
+    pending_launch: &mut Option<PendingLaunch>,
+    choice: usize,
+    tx: &UnboundedSender<Event>,
+) -> Option<Channel> {
+    pending_launch
+        .take()
+        .map(|pending| pending.choose(choice, tx))
+}
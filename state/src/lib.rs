@@ -2,7 +2,15 @@ mod app_state;
 pub mod cache;
 mod event;
 mod input_mappings;
+pub mod keymap;
+mod launch;
+mod mouse;
+mod refresh;
+mod search;
+pub mod settings;
 pub mod state;
 pub mod state_machine;
+mod toast;
 pub mod transition;
 mod util;
+mod watch;
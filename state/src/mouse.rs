@@ -0,0 +1,41 @@
+use crossterm::event::MouseEvent;
+use input::mouse::{MouseHandler, Press};
+use ui::hit::{Hits, Target};
+
+use crate::state::MoveDirection;
+
+/// What a mouse event over a view amounts to, given where things were drawn.
+pub enum Gesture<P> {
+    Tab(usize),
+    Focus(P),
+    Highlight(P, usize),
+    Open(P, usize),
+    Scroll(P, MoveDirection),
+    Choose(usize),
+}
+
+pub fn gesture<P: Copy>(
+    handler: &mut MouseHandler,
+    hits: &Hits<P>,
+    mouse_event: MouseEvent,
+) -> Option<Gesture<P>> {
+    let pointer = handler.handle(mouse_event)?;
+    let target = hits.at(pointer.column, pointer.row)?;
+
+    let gesture = match (pointer.press, target) {
+        (Press::Click | Press::DoubleClick, Target::Tab(tab)) => Gesture::Tab(tab),
+        (Press::Click | Press::DoubleClick, Target::Choice(index)) => Gesture::Choose(index),
+        (Press::Click | Press::DoubleClick, Target::Panel(panel)) => Gesture::Focus(panel),
+        (Press::Click, Target::Row(panel, index)) => Gesture::Highlight(panel, index),
+        (Press::DoubleClick, Target::Row(panel, index)) => Gesture::Open(panel, index),
+        (Press::ScrollUp, Target::Panel(panel) | Target::Row(panel, _)) => {
+            Gesture::Scroll(panel, MoveDirection::Up)
+        }
+        (Press::ScrollDown, Target::Panel(panel) | Target::Row(panel, _)) => {
+            Gesture::Scroll(panel, MoveDirection::Down)
+        }
+        (Press::ScrollUp | Press::ScrollDown, Target::Tab(_) | Target::Choice(_)) => return None,
+    };
+
+    Some(gesture)
+}
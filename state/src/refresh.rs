@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+/// When the visible channels are due to be checked again, and when they last were.
+#[derive(Clone, Copy)]
+pub struct Refresh {
+    interval: Option<Duration>,
+    next: Instant,
+    last_updated: Option<Instant>,
+}
+
+impl Refresh {
+    pub fn new(interval_seconds: u64) -> Self {
+        let interval = match interval_seconds {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        };
+
+        Self {
+            interval,
+            next: Instant::now() + interval.unwrap_or_default(),
+            last_updated: None,
+        }
+    }
+
+    pub fn due(&self) -> bool {
+        self.interval.is_some() && Instant::now() >= self.next
+    }
+
+    pub fn schedule(&mut self) {
+        if let Some(interval) = self.interval {
+            self.next = Instant::now() + interval;
+        }
+    }
+
+    pub fn updated(&mut self) {
+        self.last_updated = Some(Instant::now());
+    }
+
+    pub fn since_update(&self) -> Option<Duration> {
+        self.last_updated.map(|updated| updated.elapsed())
+    }
+}
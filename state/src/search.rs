@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use twitch::{
+    account::Account,
+    channel::Channel,
+    request::RateLimit,
+    search::{SearchUpdate, Searcher},
+};
+
+// Wait for typing to stop for this long before searching
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Channel search as you type, only asking Helix once the query stops changing.
+pub struct Search {
+    pub results: Vec<Channel>,
+    /// `None` while the search box itself is highlighted.
+    pub highlight: Option<usize>,
+    searching: bool,
+    error: Option<String>,
+    changed: Option<Instant>,
+    query: String,
+    receiver: UnboundedReceiver<SearchUpdate>,
+    sender: UnboundedSender<SearchUpdate>,
+    searcher: Searcher,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded_channel();
+
+        Self {
+            results: Vec::new(),
+            highlight: None,
+            searching: false,
+            error: None,
+            changed: None,
+            query: String::new(),
+            receiver,
+            sender,
+            searcher: Searcher::default(),
+        }
+    }
+
+    pub fn typed(&mut self) {
+        self.changed = Some(Instant::now());
+    }
+
+    // Skips the rest of the wait, for when the query is submitted
+    pub fn hurry(&mut self) -> bool {
+        if self.changed.is_none() {
+            return false;
+        }
+
+        self.changed = Instant::now().checked_sub(DEBOUNCE).or(self.changed);
+
+        true
+    }
+
+    pub fn due(&self) -> bool {
+        self.changed
+            .map_or(false, |changed| changed.elapsed() >= DEBOUNCE)
+    }
+
+    pub fn start(&mut self, query: &str, account: &Option<Account>, rate_limit: &RateLimit) {
+        self.changed = None;
+
+        let query = query.trim();
+
+        // The same query is only searched again if it failed
+        if query == self.query && self.error.is_none() {
+            return;
+        }
+
+        self.query = query.to_owned();
+        self.highlight = None;
+        self.error = None;
+
+        if query.is_empty() {
+            self.searching = false;
+            self.results.clear();
+            return;
+        }
+
+        if let Some(acc) = account {
+            self.searching = true;
+
+            self.searcher.search(acc, rate_limit, query, &self.sender);
+        }
+    }
+
+    pub fn receive(&mut self) {
+        while let Ok((query, results)) = self.receiver.try_recv() {
+            // Typed some more since this one was sent
+            if query != self.query {
+                continue;
+            }
+
+            self.searching = false;
+
+            match results {
+                Ok(channels) => {
+                    self.results = channels;
+                    self.highlight = self.highlight.filter(|index| *index < self.results.len());
+                }
+                Err(e) => {
+                    self.results.clear();
+                    self.highlight = None;
+                    self.error = Some(e);
+                }
+            }
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Channel> {
+        self.results.get(self.highlight?)
+    }
+
+    // What to show instead of the results, if anything
+    pub fn message(&self) -> Option<String> {
+        if self.searching {
+            return Some(String::from("Searching..."));
+        }
+
+        if let Some(e) = &self.error {
+            return Some(e.clone());
+        }
+
+        if self.results.is_empty() && !self.query.is_empty() {
+            return Some(String::from("No channels found"));
+        }
+
+        None
+    }
+}
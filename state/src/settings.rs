@@ -0,0 +1,69 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use twitch::{eventsub, paths, storage};
+use ui::column::Column;
+
+const SETTINGS_FILE: &str = "settings.toml";
+
+// Seconds between status refreshes, 0 turns them off
+const DEFAULT_REFRESH_INTERVAL: u64 = 60;
+// Seconds between checks of the channels to notify about, kept apart from the refresh so turning
+// that off doesn't turn notifications off with it
+const DEFAULT_NOTIFY_INTERVAL: u64 = 60;
+// Seconds a checked status is reused for instead of asking Twitch again
+const DEFAULT_STATUS_TTL: u64 = 30;
+// What gets written to the log file, in `RUST_LOG` syntax
+const DEFAULT_LOG: &str = "info";
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub refresh_interval: u64,
+    pub notify_interval: u64,
+    pub status_ttl: u64,
+    pub persist_statuses: bool,
+    pub columns: Vec<Column>,
+    pub log: String,
+    // Statuses pushed by Twitch as they change, on top of the refresh
+    pub eventsub: bool,
+    pub eventsub_url: String,
+    pub eventsub_subscriptions_url: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            notify_interval: DEFAULT_NOTIFY_INTERVAL,
+            status_ttl: DEFAULT_STATUS_TTL,
+            persist_statuses: false,
+            columns: Column::defaults(),
+            log: String::from(DEFAULT_LOG),
+            eventsub: true,
+            eventsub_url: String::from(eventsub::WEBSOCKET_URL),
+            eventsub_subscriptions_url: String::from(eventsub::SUBSCRIPTIONS_URL),
+        }
+    }
+}
+
+impl Settings {
+    #[allow(clippy::missing_errors_doc)]
+    pub fn load_from_file(file: &Path) -> Result<Self> {
+        storage::load(file)
+    }
+
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from_file(&paths::config_file(SETTINGS_FILE)).unwrap_or_default()
+    }
+
+    /// Where to open the EventSub session, unless it is turned off.
+    #[must_use]
+    pub fn eventsub_endpoints(&self) -> Option<eventsub::Endpoints> {
+        self.eventsub.then(|| eventsub::Endpoints {
+            websocket: self.eventsub_url.clone(),
+            subscriptions: self.eventsub_subscriptions_url.clone(),
+        })
+    }
+}
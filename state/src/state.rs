@@ -1,6 +1,8 @@
 use crate::{
     app_state::{
+        browse::Browse,
         exit::Exit,
+        follows::Follows,
         home::Home,
         lists::Lists,
         popup::Popup,
@@ -10,27 +12,38 @@ use crate::{
     transition::Transition,
 };
 use async_trait::async_trait;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use std::fmt::{Display, Formatter, Result};
 use tokio::sync::mpsc::UnboundedSender;
 use tui::{backend::Backend, terminal::Frame};
-use twitch::account::Account;
+use twitch::{account::Account, status_cache::StatusCache};
 use ui::theme::Theme;
 
 #[async_trait]
 pub trait State {
     async fn tick(&self, account: &Option<Account>, timer: u64, events: UnboundedSender<Event>);
 
-    fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64);
+    fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64);
 
     fn transition(
-        &self,
+        &mut self,
         event: Event,
         account: &Option<Account>,
+        statuses: &StatusCache,
         tx: UnboundedSender<Event>,
     ) -> Option<Transition>;
 
-    fn handle(&self, key_event: KeyEvent) -> Option<Event>;
+    fn handle(&mut self, key_event: KeyEvent) -> Vec<Event>;
+
+    /// Actions for keys that were waiting on more keys that never came.
+    fn flush(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+
+    /// Actions for a mouse event, going by where things were last drawn.
+    fn mouse(&mut self, _: MouseEvent) -> Vec<Event> {
+        Vec::new()
+    }
 
     fn process(&mut self, action: Event, tx: &UnboundedSender<Event>);
 }
@@ -42,7 +55,8 @@ pub enum AppState {
     Home(Home),
     Popup(Popup),
     Lists(Lists),
-    // Follows(StateFollows),
+    Follows(Follows),
+    Browse(Browse),
     Exit(Exit),
 }
 
@@ -54,53 +68,95 @@ impl AppState {
             Self::Home(state) => state.tick(account, timer, tx).await,
             Self::Popup(state) => state.tick(account, timer, tx).await,
             Self::Lists(state) => state.tick(account, timer, tx).await,
+            Self::Follows(state) => state.tick(account, timer, tx).await,
+            Self::Browse(state) => state.tick(account, timer, tx).await,
             Self::Exit(state) => state.tick(account, timer, tx).await,
         }
     }
 
-    pub fn render<B: Backend>(&self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
+    pub fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>, timer: u64) {
         match self {
             Self::AccountMissing(state) => state.render(theme, frame, timer),
             Self::Startup(state) => state.render(theme, frame, timer),
             Self::Home(state) => state.render(theme, frame, timer),
             Self::Popup(state) => state.render(theme, frame, timer),
             Self::Lists(state) => state.render(theme, frame, timer),
+            Self::Follows(state) => state.render(theme, frame, timer),
+            Self::Browse(state) => state.render(theme, frame, timer),
             Self::Exit(state) => state.render(theme, frame, timer),
         }
     }
 
     #[must_use]
     pub fn transition(
-        &self,
+        &mut self,
         account: &Option<Account>,
+        statuses: &StatusCache,
         event: Event,
         events_sender: UnboundedSender<Event>,
     ) -> Option<Transition> {
         match self {
-            Self::AccountMissing(state) => state.transition(event, account, events_sender),
-            Self::Startup(state) => state.transition(event, account, events_sender),
-            Self::Home(state) => state.transition(event, account, events_sender),
-            Self::Popup(state) => state.transition(event, account, events_sender),
-            Self::Lists(state) => state.transition(event, account, events_sender),
-            Self::Exit(state) => state.transition(event, account, events_sender),
+            Self::AccountMissing(state) => {
+                state.transition(event, account, statuses, events_sender)
+            }
+            Self::Startup(state) => state.transition(event, account, statuses, events_sender),
+            Self::Home(state) => state.transition(event, account, statuses, events_sender),
+            Self::Popup(state) => state.transition(event, account, statuses, events_sender),
+            Self::Lists(state) => state.transition(event, account, statuses, events_sender),
+            Self::Follows(state) => state.transition(event, account, statuses, events_sender),
+            Self::Browse(state) => state.transition(event, account, statuses, events_sender),
+            Self::Exit(state) => state.transition(event, account, statuses, events_sender),
         }
     }
 
-    pub fn handle(&mut self, key_event: KeyEvent) -> Option<Event> {
+    pub fn handle(&mut self, key_event: KeyEvent) -> Vec<Event> {
         match self {
             Self::AccountMissing(state) => state.handle(key_event),
             Self::Startup(state) => state.handle(key_event),
             Self::Home(state) => state.handle(key_event),
             Self::Popup(state) => state.handle(key_event),
             Self::Lists(state) => state.handle(key_event),
+            Self::Follows(state) => state.handle(key_event),
+            Self::Browse(state) => state.handle(key_event),
             Self::Exit(state) => state.handle(key_event),
         }
     }
 
+    pub fn flush(&mut self) -> Vec<Event> {
+        match self {
+            Self::AccountMissing(state) => state.flush(),
+            Self::Startup(state) => state.flush(),
+            Self::Home(state) => state.flush(),
+            Self::Popup(state) => state.flush(),
+            Self::Lists(state) => state.flush(),
+            Self::Follows(state) => state.flush(),
+            Self::Browse(state) => state.flush(),
+            Self::Exit(state) => state.flush(),
+        }
+    }
+
+    pub fn mouse(&mut self, mouse_event: MouseEvent) -> Vec<Event> {
+        match self {
+            Self::AccountMissing(state) => state.mouse(mouse_event),
+            Self::Startup(state) => state.mouse(mouse_event),
+            Self::Home(state) => state.mouse(mouse_event),
+            Self::Popup(state) => state.mouse(mouse_event),
+            Self::Lists(state) => state.mouse(mouse_event),
+            Self::Follows(state) => state.mouse(mouse_event),
+            Self::Browse(state) => state.mouse(mouse_event),
+            Self::Exit(state) => state.mouse(mouse_event),
+        }
+    }
+
     pub fn receive(&mut self) {
         match self {
-            Self::Home(state) => state.channel_check(),
+            Self::Home(state) => {
+                state.channel_check();
+                state.search_check();
+            }
             Self::Lists(state) => state.channel_check(),
+            Self::Follows(state) => state.follows_check(),
+            Self::Browse(state) => state.browse_check(),
             Self::AccountMissing(_) | Self::Startup(_) | Self::Popup(_) | Self::Exit(_) => {}
         }
     }
@@ -112,6 +168,8 @@ impl AppState {
             Self::Home(state) => state.process(action, tx),
             Self::Popup(state) => state.process(action, tx),
             Self::Lists(state) => state.process(action, tx),
+            Self::Follows(state) => state.process(action, tx),
+            Self::Browse(state) => state.process(action, tx),
             Self::Exit(state) => state.process(action, tx),
         }
     }
@@ -1,16 +1,35 @@
 use crate::{
-    app_state::startup::{AccountMissing, Startup},
+    app_state::{
+        popup::Popup,
+        startup::{AccountMissing, Startup},
+    },
     cache::Cache,
     event::Event,
+    keymap::Keymap,
+    settings::Settings,
     state::AppState,
+    toast::Toasts,
     transition::Transition,
+    watch::Watch,
 };
 use app_event::event::Event as AppEvent;
-use crossterm::event::KeyEvent;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use crossterm::event::{KeyEvent, MouseEvent};
+use std::time::Duration;
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    watch as tracked,
+};
+use tracing::{error, trace};
 use tui::{backend::Backend, terminal::Frame};
-use twitch::account::Account;
-use ui::theme::Theme;
+use twitch::{
+    account::Account,
+    channel::Channel,
+    eventsub::{self, Endpoints, StreamEvent},
+    notifier::Desktop,
+    status_cache::StatusCache,
+    status_service::StatusUpdate,
+};
+use ui::{render, theme::Theme, toast::Severity};
 
 pub struct StateMachine {
     state: AppState,
@@ -21,6 +40,19 @@ pub struct StateMachine {
     app_events: UnboundedSender<AppEvent>,
     timer: u64,
     paste_receiver: UnboundedReceiver<String>,
+    statuses: StatusCache,
+    toasts: Toasts,
+    // Unlike `timer` this isn't reset by transitions, so toasts last across them
+    ticks: u64,
+    watch: Watch<Desktop>,
+    // Taken once the session is started, which waits for an account
+    eventsub: Option<Endpoints>,
+    stream_events: UnboundedReceiver<StreamEvent>,
+    stream_events_sender: UnboundedSender<StreamEvent>,
+    stream_checks: UnboundedReceiver<StatusUpdate>,
+    stream_checks_sender: UnboundedSender<StatusUpdate>,
+    // Handles EventSub follows, sent again whenever favourites or lists are saved
+    tracked: tracked::Sender<Vec<String>>,
 }
 
 impl StateMachine {
@@ -31,6 +63,16 @@ impl StateMachine {
         paste_receiver: UnboundedReceiver<String>,
     ) -> Self {
         let (sender, receiver) = unbounded_channel();
+        let (stream_events_sender, stream_events) = unbounded_channel();
+        let (stream_checks_sender, stream_checks) = unbounded_channel();
+
+        // Normally installed while checking it at startup, which only reads the file once
+        if Keymap::installed().is_none() {
+            Keymap::load().install();
+        }
+
+        let settings = Settings::load();
+        let status_ttl = Duration::from_secs(settings.status_ttl);
 
         Self {
             state: if account_loaded {
@@ -45,11 +87,36 @@ impl StateMachine {
             app_events,
             timer: 0,
             paste_receiver,
+            statuses: if settings.persist_statuses {
+                StatusCache::persistent(status_ttl)
+            } else {
+                StatusCache::new(status_ttl)
+            },
+            toasts: Toasts::default(),
+            ticks: 0,
+            watch: Watch::new(settings.notify_interval, Desktop),
+            eventsub: settings.eventsub_endpoints(),
+            stream_events,
+            stream_events_sender,
+            stream_checks,
+            stream_checks_sender,
+            tracked: tracked::channel(tracked_handles()).0,
         }
     }
 
     pub async fn tick(&mut self, account: &Option<Account>) {
         self.timer += 1;
+        self.ticks += 1;
+
+        self.toasts.expire(self.ticks);
+
+        self.watch.tick(account, &self.statuses);
+
+        self.receive_stream_events(account);
+
+        for action in self.state.flush() {
+            self.state.process(action, &self.events_sender);
+        }
 
         self.state
             .tick(account, self.timer, self.events_sender.clone())
@@ -58,7 +125,8 @@ impl StateMachine {
         let event = self.events.try_recv();
 
         if let Ok(e) = event {
-            #[allow(clippy::single_match)]
+            trace!("{e}");
+
             match &e {
                 Event::Exited => {
                     let _result = self.app_events.send(AppEvent::Exit);
@@ -68,13 +136,49 @@ impl StateMachine {
                         .app_events
                         .send(AppEvent::SetAccount(new_account.clone()));
                 }
+                Event::ThemeChoice(name) => {
+                    if let Some(theme) = Theme::named(name) {
+                        if let Err(e) = theme.save() {
+                            error!("Could not save the theme: {e:#}");
+
+                            self.toasts.add(
+                                Severity::Warning,
+                                String::from("Theme Not Saved"),
+                                format!("{e:#}"),
+                                self.ticks,
+                            );
+                        }
+
+                        let _result = self.app_events.send(AppEvent::SetTheme(theme));
+                    }
+                }
+                Event::ChannelsSaved => {
+                    self.tracked.send_replace(tracked_handles());
+                    self.watch.reload();
+                }
+                Event::Notify(severity, title, message) => {
+                    self.toasts
+                        .add(*severity, title.clone(), message.clone(), self.ticks);
+                }
                 _ => {}
             }
 
-            if let Some(transition) = self
-                .state
-                .transition(account, e, self.events_sender.clone())
-            {
+            let transition = match e {
+                // Shown over whichever view asked for it
+                Event::ShowNotifications => {
+                    Some(Transition::Push(AppState::Popup(Popup::new_choice(
+                        String::from("Notifications"),
+                        String::from("Most recent first"),
+                        &self.toasts.history(),
+                        None,
+                    ))))
+                }
+                e => self
+                    .state
+                    .transition(account, &self.statuses, e, self.events_sender.clone()),
+            };
+
+            if let Some(transition) = transition {
                 match transition {
                     Transition::Push(state) => {
                         let old_state = std::mem::replace(&mut self.state, state);
@@ -102,12 +206,74 @@ impl StateMachine {
         };
     }
 
+    // Statuses EventSub pushed go to the cache, the watched channels and then the current view
+    fn receive_stream_events(&mut self, account: &Option<Account>) {
+        if let Some(acc) = account {
+            if let Some(endpoints) = self.eventsub.take() {
+                eventsub::listen(
+                    endpoints,
+                    acc,
+                    self.statuses.rate_limit(),
+                    self.tracked.subscribe(),
+                    self.stream_events_sender.clone(),
+                );
+            }
+        }
+
+        while let Ok(event) = self.stream_events.try_recv() {
+            match event {
+                // Helix has the title, game and viewers the notification and columns need
+                StreamEvent::Online { handle, .. } => {
+                    self.statuses.expire(&handle);
+
+                    if let Some(acc) = account {
+                        Channel::check(
+                            &[Channel::new(handle.clone(), handle)],
+                            acc,
+                            &self.statuses,
+                            &self.stream_checks_sender,
+                        );
+                    }
+                }
+                event => {
+                    if let Some(update) = self.statuses.change(&event) {
+                        self.status_changed(update);
+                    }
+                }
+            }
+        }
+
+        while let Ok(update) = self.stream_checks.try_recv() {
+            self.status_changed(update);
+        }
+    }
+
+    fn status_changed(&mut self, update: StatusUpdate) {
+        self.watch.update(&update);
+
+        let _result = self.events_sender.send(Event::StatusChanged(update));
+    }
+
     pub fn render<B: Backend>(&mut self, theme: &Theme, frame: &mut Frame<'_, B>) {
         self.state.render(theme, frame, self.timer);
+
+        render::toasts(theme, frame, self.toasts.shown(), self.ticks);
+    }
+
+    /// Shows a toast over whatever is on screen.
+    pub fn notify(&mut self, severity: Severity, title: &str, message: &str) {
+        self.toasts
+            .add(severity, title.to_owned(), message.to_owned(), self.ticks);
     }
 
     pub fn handle(&mut self, key_event: KeyEvent) {
-        if let Some(action) = self.state.handle(key_event) {
+        for action in self.state.handle(key_event) {
+            self.state.process(action, &self.events_sender);
+        }
+    }
+
+    pub fn mouse(&mut self, mouse_event: MouseEvent) {
+        for action in self.state.mouse(mouse_event) {
             self.state.process(action, &self.events_sender);
         }
     }
@@ -120,3 +286,10 @@ impl StateMachine {
         self.state.receive();
     }
 }
+
+fn tracked_handles() -> Vec<String> {
+    Channel::tracked()
+        .into_iter()
+        .map(|channel| channel.handle)
+        .collect()
+}
@@ -0,0 +1,91 @@
+use crate::event::Event;
+use std::collections::VecDeque;
+use tokio::sync::mpsc::UnboundedSender;
+use twitch::channel::Channel;
+use ui::toast::{Severity, Toast};
+
+// Older notifications are forgotten once there are this many
+const HISTORY_LENGTH: usize = 50;
+const EMPTY_HISTORY: &str = "Nothing yet";
+
+/// The toasts on screen and every one shown recently, oldest first.
+#[derive(Default)]
+pub struct Toasts {
+    shown: Vec<Toast>,
+    history: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn add(&mut self, severity: Severity, title: String, message: String, now: u64) {
+        // The same thing happening again keeps its toast up instead of stacking another
+        if let Some(toast) = self.shown.iter_mut().find(|toast| {
+            toast.severity == severity && toast.title == title && toast.message == message
+        }) {
+            toast.shown = now;
+            return;
+        }
+
+        let toast = Toast {
+            severity,
+            title,
+            message,
+            shown: now,
+        };
+
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(toast.clone());
+        self.shown.push(toast);
+    }
+
+    pub fn expire(&mut self, now: u64) {
+        self.shown.retain(|toast| !toast.expired(now));
+    }
+
+    #[must_use]
+    pub fn shown(&self) -> &[Toast] {
+        &self.shown
+    }
+
+    /// A line per notification, newest first, for the history popup.
+    #[must_use]
+    pub fn history(&self) -> Vec<String> {
+        if self.history.is_empty() {
+            return vec![String::from(EMPTY_HISTORY)];
+        }
+
+        self.history
+            .iter()
+            .rev()
+            .map(|toast| format!("{}: {} - {}", toast.severity, toast.title, toast.message))
+            .collect()
+    }
+}
+
+pub fn notify(tx: &UnboundedSender<Event>, severity: Severity, title: &str, message: &str) {
+    let _result = tx.send(Event::Notify(
+        severity,
+        title.to_owned(),
+        message.to_owned(),
+    ));
+}
+
+pub fn notify_toggled(tx: &UnboundedSender<Event>, channel: &Channel) {
+    if channel.notify {
+        notify(
+            tx,
+            Severity::Info,
+            "Notifications On",
+            &format!("{} will notify you when going live", channel.friendly_name),
+        );
+    } else {
+        notify(
+            tx,
+            Severity::Info,
+            "Notifications Off",
+            &format!("{} won't notify you anymore", channel.friendly_name),
+        );
+    }
+}
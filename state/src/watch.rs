@@ -0,0 +1,84 @@
+use crate::refresh::Refresh;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use twitch::{
+    account::Account,
+    channel::Channel,
+    notifier::{LiveWatch, Notifier},
+    status_cache::StatusCache,
+    status_service::StatusUpdate,
+};
+
+/// Keeps checking the channels that asked to be notified, whichever tab is open, and tells
+/// `notifier` when one of them goes live.
+pub struct Watch<N: Notifier> {
+    channels: Vec<Channel>,
+    live: LiveWatch,
+    refresh: Refresh,
+    channel_check: UnboundedReceiver<StatusUpdate>,
+    channel_check_sender: UnboundedSender<StatusUpdate>,
+    notifier: N,
+}
+
+impl<N: Notifier> Watch<N> {
+    pub fn new(interval: u64, notifier: N) -> Self {
+        let (sender, receiver) = unbounded_channel();
+
+        Self {
+            channels: notified(),
+            live: LiveWatch::default(),
+            refresh: Refresh::new(interval),
+            channel_check: receiver,
+            channel_check_sender: sender,
+            notifier,
+        }
+    }
+
+    pub fn tick(&mut self, account: &Option<Account>, statuses: &StatusCache) {
+        while let Ok(update) = self.channel_check.try_recv() {
+            self.update(&update);
+        }
+
+        if !self.refresh.due() {
+            return;
+        }
+
+        self.refresh.schedule();
+
+        // Read every time so channels opted in or out since apply straight away
+        self.channels = notified();
+
+        if let Some(acc) = account {
+            if !self.channels.is_empty() {
+                Channel::check(&self.channels, acc, statuses, &self.channel_check_sender);
+            }
+        }
+    }
+
+    /// Reads which channels asked to be notified again, for when they were just saved.
+    pub fn reload(&mut self) {
+        self.channels = notified();
+    }
+
+    /// Takes in a status from anywhere, not just the checks made here.
+    pub fn update(&mut self, update: &StatusUpdate) {
+        let channel = match self
+            .channels
+            .iter()
+            .find(|channel| channel.handle.eq_ignore_ascii_case(&update.0))
+        {
+            Some(channel) => channel,
+            None => return,
+        };
+
+        if let Some(notification) = self.live.update(channel, update) {
+            self.notifier.notify(notification);
+        }
+    }
+}
+
+fn notified() -> Vec<Channel> {
+    Channel::tracked()
+        .into_iter()
+        .filter(|channel| channel.notify)
+        .collect()
+}
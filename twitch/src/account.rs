@@ -1,18 +1,20 @@
-use crate::secret::{Expose, Secret};
+use crate::{
+    paths,
+    secret::{Expose, Secret},
+    storage,
+};
 use anyhow::{Context, Result};
 use open;
 use serde::{Deserialize, Serialize};
 use server::server::Server;
-use std::{
-    fs::{copy, read_to_string, write},
-    path::Path,
-};
+use tracing::info;
 use twitch_api::twitch_oauth2::{
-    AccessToken, ClientId, ClientSecret, RefreshToken, TwitchToken, UserToken,
+    AccessToken, ClientId, ClientSecret, RefreshToken, Scope, TwitchToken, UserToken,
 };
 
-const ACCOUNT_FILE: &str = "account.json";
-const ACCOUNT_DIST_FILE: &str = "account.json.dist";
+const ACCOUNT_FILE: &str = "account.toml";
+// Written out as the account file the first time, to be filled in when logging in
+const ACCOUNT_TEMPLATE: &str = include_str!("../account.toml.dist");
 
 #[must_use]
 #[derive(Serialize, Deserialize, Clone)]
@@ -29,8 +31,10 @@ pub struct Account {
 impl Account {
     #[allow(clippy::missing_errors_doc)]
     pub async fn load() -> Result<Self> {
-        if !Path::new(ACCOUNT_FILE).exists() {
-            copy(ACCOUNT_DIST_FILE, ACCOUNT_FILE)?;
+        let file = paths::config_file(ACCOUNT_FILE);
+
+        if !storage::exists(&file) {
+            storage::write_atomic(&file, ACCOUNT_TEMPLATE)?;
         }
 
         let mut account: Self = load_account()?;
@@ -40,12 +44,17 @@ impl Account {
         Ok(account)
     }
 
+    /// Whether the account file has been through a login, so failing to load it is worth telling.
+    #[must_use]
+    pub fn is_set_up() -> bool {
+        load_account().map_or(false, |account| {
+            !account.user_access_token.expose_value().is_empty()
+        })
+    }
+
     #[allow(clippy::missing_errors_doc)]
     pub fn save(&self) -> Result<()> {
-        let file_contents: String = serde_json::to_string_pretty(&self)?;
-
-        write(ACCOUNT_FILE, file_contents)?;
-        Ok(())
+        storage::save(&paths::config_file(ACCOUNT_FILE), self)
     }
 
     #[must_use]
@@ -53,6 +62,11 @@ impl Account {
         self.user_access_token.clone()
     }
 
+    #[must_use]
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
     #[allow(clippy::missing_errors_doc)]
     pub async fn new(
         username: String,
@@ -79,7 +93,9 @@ impl Account {
             ClientId::new(account.client_id.expose_value().to_owned()),
             ClientSecret::new(account.client_secret.expose_value().to_owned()),
             url,
-        );
+        )
+        // Needed for the Follows tab
+        .set_scopes(vec![Scope::UserReadFollows]);
 
         let (auth_url, _) = builder.generate_url();
 
@@ -100,16 +116,14 @@ impl Account {
 
         account.save()?;
 
+        info!("Logged in as {}", account.username);
+
         Ok(account)
     }
 }
 
 fn load_account() -> Result<Account> {
-    let data: String = read_to_string(ACCOUNT_FILE)?;
-
-    let account: Account = serde_json::from_str(data.as_str())?;
-
-    Ok(account)
+    storage::load(&paths::config_file(ACCOUNT_FILE))
 }
 
 async fn check_token(account: &mut Account) -> Result<()> {
@@ -139,7 +153,8 @@ async fn check_token(account: &mut Account) -> Result<()> {
 
         user_token
             .refresh_token(&reqwest::Client::default())
-            .await?;
+            .await
+            .with_context(|| "Could not refresh the access token")?;
 
         account.user_access_token = Secret::new(user_token.access_token.secret().to_owned());
         account.refresh_token = match user_token.refresh_token {
@@ -0,0 +1,208 @@
+use crate::{
+    account::Account,
+    channel::{status::Status, stream::StreamInfo, Channel},
+    request::RateLimit,
+    status_cache::StatusCache,
+    status_service::{SharedService, StatusService, Stream},
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::{spawn, sync::mpsc::UnboundedSender};
+use twitch_api::{
+    helix::{Request, RequestGet},
+    twitch_oauth2::Scope,
+};
+
+const PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+}
+
+/// One page of results and the cursor for the next, if there is one.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub cursor: Option<String>,
+}
+
+pub enum BrowseUpdate {
+    Categories(std::result::Result<Page<Category>, String>),
+    /// Tagged with the category id, so pages for a category no longer open can be dropped.
+    Streams(String, std::result::Result<Page<Channel>, String>),
+}
+
+#[derive(Serialize)]
+struct GetTopGames {
+    first: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+impl Request for GetTopGames {
+    const PATH: &'static str = "games/top";
+    const SCOPE: &'static [Scope] = &[];
+
+    type Response = Vec<Category>;
+}
+
+impl RequestGet for GetTopGames {}
+
+// Helix already returns these ordered by viewers
+#[derive(Serialize)]
+struct GetCategoryStreams<'a> {
+    game_id: &'a str,
+    first: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+impl Request for GetCategoryStreams<'_> {
+    const PATH: &'static str = "streams";
+    const SCOPE: &'static [Scope] = &[];
+
+    type Response = Vec<Stream>;
+}
+
+impl RequestGet for GetCategoryStreams<'_> {}
+
+/// The most watched categories right now, a page at a time.
+#[allow(clippy::missing_errors_doc)]
+pub async fn top_categories(
+    service: &StatusService,
+    after: Option<String>,
+) -> Result<Page<Category>> {
+    let req = GetTopGames {
+        first: PAGE_SIZE,
+        after,
+    };
+
+    let response = service
+        .helix()
+        .req_get(req, service.token())
+        .await
+        .with_context(|| "Could not get top categories")?;
+
+    Ok(Page {
+        items: response.data,
+        cursor: next_cursor(response.pagination),
+    })
+}
+
+/// Live streams in a category, most viewers first, a page at a time.
+#[allow(clippy::missing_errors_doc)]
+pub async fn streams(
+    service: &StatusService,
+    category_id: &str,
+    after: Option<String>,
+) -> Result<Page<Channel>> {
+    let req = GetCategoryStreams {
+        game_id: category_id,
+        first: PAGE_SIZE,
+        after,
+    };
+
+    let response = service
+        .helix()
+        .req_get(req, service.token())
+        .await
+        .with_context(|| "Could not get streams for category")?;
+
+    let channels = response
+        .data
+        .iter()
+        .map(|stream| {
+            let mut channel = Channel::new(stream.user_name.clone(), stream.user_login.clone());
+            channel.status = Status::Online;
+            channel.stream = Some(StreamInfo::from(stream));
+            channel
+        })
+        .collect();
+
+    Ok(Page {
+        items: channels,
+        cursor: next_cursor(response.pagination),
+    })
+}
+
+/// Fetches a page of categories in the background, with the token `service` validated for an
+/// earlier page if there was one.
+pub fn fetch_categories(
+    account: &Account,
+    service: &SharedService,
+    rate_limit: &RateLimit,
+    after: Option<String>,
+    sender: &UnboundedSender<BrowseUpdate>,
+) {
+    let account = account.clone();
+    let service = service.clone();
+    let rate_limit = rate_limit.clone();
+    let tx = (*sender).clone();
+
+    spawn(async move {
+        let page = async {
+            let validated = service.get(&account, &rate_limit).await?;
+
+            top_categories(&validated, after).await
+        }
+        .await;
+
+        // The token may be why it failed, so it is validated again for the next page
+        if page.is_err() {
+            service.forget().await;
+        }
+
+        let page = page.map_err(|e| format!("{e:#}"));
+
+        let _result = tx.send(BrowseUpdate::Categories(page));
+    });
+}
+
+/// Fetches a page of streams in the background, keeping `statuses` up to date with them. Like
+/// [`fetch_categories`], the token `service` holds is reused.
+pub fn fetch_streams(
+    account: &Account,
+    service: &SharedService,
+    statuses: &StatusCache,
+    category_id: &str,
+    after: Option<String>,
+    sender: &UnboundedSender<BrowseUpdate>,
+) {
+    let account = account.clone();
+    let service = service.clone();
+    let statuses = statuses.clone();
+    let category_id = category_id.to_owned();
+    let tx = (*sender).clone();
+
+    spawn(async move {
+        let page = async {
+            let validated = service.get(&account, statuses.rate_limit()).await?;
+
+            streams(&validated, &category_id, after).await
+        }
+        .await;
+
+        let page = match page {
+            Ok(page) => {
+                statuses.store_channels(&page.items);
+
+                Ok(page)
+            }
+            Err(e) => {
+                // The token may be why it failed, so it is validated again for the next page
+                service.forget().await;
+
+                Err(format!("{e:#}"))
+            }
+        };
+
+        let _result = tx.send(BrowseUpdate::Streams(category_id, page));
+    });
+}
+
+fn next_cursor<C: ToString>(cursor: Option<C>) -> Option<String> {
+    cursor
+        .map(|cursor| cursor.to_string())
+        .filter(|cursor| !cursor.is_empty())
+}
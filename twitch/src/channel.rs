@@ -1,144 +1,369 @@
 pub mod status;
+pub mod stream;
 
 use crate::{
     account::Account,
-    secret::{Expose, Secret},
+    launcher::{self, Error as LaunchError, Launcher},
+    paths,
+    player::Player,
+    secret::Secret,
+    status_cache::StatusCache,
+    status_service::{self, StatusService, StatusUpdate},
+    storage,
 };
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use status::Status;
 use std::{
-    fs::read_to_string,
-    io::Result,
-    process::{Command, Output},
+    fs::read_dir,
+    path::{Path, PathBuf},
+    time::Duration,
 };
-use tokio::{spawn, sync::mpsc::UnboundedSender};
-use twitch_api::{
-    helix::streams::GetStreamsRequest,
-    twitch_oauth2::{AccessToken, UserToken},
-    HelixClient,
+use stream::StreamInfo;
+use tokio::{
+    spawn,
+    sync::mpsc::{unbounded_channel, UnboundedSender},
 };
+use tracing::{error, info, warn};
+
+const FAVOURITES_FILE: &str = "favourites.toml";
+const LISTS_DIR: &str = "lists";
+pub const LIST_FILE_EXTENSION: &str = ".toml";
+// Lists still in JSON are migrated the first time they're loaded
+const LEGACY_LIST_FILE_EXTENSION: &str = ".json";
 
 #[derive(Clone)]
 pub struct List {
     pub channels: Vec<Channel>,
     pub name: String,
     pub path: String,
+    pub player: Option<String>,
+}
+
+// Favourites are stored the same way, just never with a player
+#[derive(Serialize, Deserialize)]
+#[serde(from = "ListFormat")]
+struct ListFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    player: Option<String>,
+    channels: Vec<Channel>,
+}
+
+// Lists used to be a bare array of channels, which old JSON files can still be
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ListFormat {
+    Channels(Vec<Channel>),
+    WithSettings {
+        channels: Vec<Channel>,
+        #[serde(default)]
+        player: Option<String>,
+    },
+}
+
+impl From<ListFormat> for ListFile {
+    fn from(format: ListFormat) -> Self {
+        match format {
+            ListFormat::Channels(channels) => Self {
+                player: None,
+                channels,
+            },
+            ListFormat::WithSettings { channels, player } => Self { player, channels },
+        }
+    }
+}
+
+impl List {
+    #[must_use]
+    pub const fn new(name: String, path: String) -> Self {
+        Self {
+            channels: Vec::new(),
+            name,
+            path,
+            player: None,
+        }
+    }
+
+    /// Where every list file is kept.
+    #[must_use]
+    pub fn dir() -> PathBuf {
+        paths::data_file(LISTS_DIR)
+    }
+
+    #[must_use]
+    pub fn file(&self) -> PathBuf {
+        Self::dir().join(&self.path)
+    }
+
+    /// Every list in the lists directory, sorted by name. A list that can't be read is kept, empty,
+    /// so it still shows up.
+    #[must_use]
+    pub fn load_all() -> Vec<Self> {
+        let mut names = read_dir(Self::dir())
+            .into_iter()
+            .flatten()
+            .filter_map(std::result::Result::ok)
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|file_name| {
+                file_name
+                    .strip_suffix(LIST_FILE_EXTENSION)
+                    .or_else(|| file_name.strip_suffix(LEGACY_LIST_FILE_EXTENSION))
+                    .map(str::to_owned)
+            })
+            .collect::<Vec<String>>();
+
+        // Both files can be there for one list, the TOML one is what gets loaded
+        names.sort();
+        names.dedup();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let path = format!("{name}{LIST_FILE_EXTENSION}");
+
+                Self::load_from_file(name.clone(), path.clone(), &Self::dir().join(&path))
+                    .unwrap_or_else(|e| {
+                        warn!("Could not load list {name}: {e:#}");
+                        Self::new(name, path)
+                    })
+            })
+            .collect()
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    pub fn load_from_file(name: String, path: String, file: &Path) -> Result<Self> {
+        let contents: ListFile = storage::load(file)?;
+
+        Ok(Self {
+            channels: contents.channels,
+            name,
+            path,
+            player: contents.player,
+        })
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    pub fn save_to_file(&self, file: &Path) -> Result<()> {
+        storage::save(
+            file,
+            &ListFile {
+                player: self.player.clone(),
+                channels: self.channels.clone(),
+            },
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Channel {
     pub friendly_name: String,
     pub handle: String,
-    #[serde(default = "Status::default")]
+    #[serde(default = "Status::default", skip_serializing)]
     pub status: Status,
-    pub game: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub stream: Option<StreamInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    // Whether to show a desktop notification when the channel goes live
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub notify: bool,
 }
 
 impl Channel {
     #[must_use]
-    pub fn new(friendly_name: String, handle: String, game: Option<String>) -> Self {
+    pub fn new(friendly_name: String, handle: String) -> Self {
         Self {
             friendly_name,
             handle,
             status: Status::default(),
-            game,
+            stream: None,
+            player: None,
+            quality: None,
+            notify: false,
         }
     }
 
-    pub async fn update_status_and_game(
-        handle: String,
-        user_access_token: Secret,
-    ) -> (Status, Option<String>) {
-        let access_token = AccessToken::new(user_access_token.expose_value().to_owned());
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("https://twitch.tv/{}", self.handle)
+    }
 
-        let client: HelixClient<reqwest::Client> = HelixClient::new();
+    // TODO add support for currently hosting?
 
-        let user_token =
-            match UserToken::from_existing(&reqwest::Client::default(), access_token, None, None)
-                .await
-            {
-                Ok(token) => token,
-                Err(e) => {
-                    eprintln!("Could not validate token while updating channel status: {e}");
-                    return (Status::Unknown, None);
-                }
-            };
+    // TODO popup if channel is offline (are you sure?)
+    #[allow(clippy::missing_errors_doc)]
+    pub fn launch(&self, player: &Player) -> std::result::Result<(), LaunchError> {
+        let (program, args) = player
+            .command(self)
+            .ok_or_else(|| LaunchError::EmptyCommand(player.name.clone()))?;
 
-        let req = GetStreamsRequest::user_logins(vec![(&handle).into()]);
+        info!("Launching {} with {}", self.handle, player.name);
 
-        let response = match client.req_get(req, &user_token).await {
-            Ok(response) => response,
-            Err(e) => {
-                eprintln!("Could not get channel status: {e}");
-                return (Status::Unknown, None);
-            }
-        };
+        launcher::native().spawn(program.as_str(), &args)
+    }
 
-        let stream = response
-            .data
-            .into_iter()
-            .find(|stream| stream.user_login.to_string() == handle);
+    #[allow(clippy::missing_errors_doc)]
+    pub fn launch_chat(&self) -> std::result::Result<(), LaunchError> {
+        launcher::native().launch_chat(&self.handle)
+    }
 
-        if let Some(stream_data) = stream {
-            (Status::Online, Some(stream_data.game_name))
-        } else {
-            (Status::Offline, None)
+    /// Takes the status and stream from `update` if it is about this channel. Helix and EventSub
+    /// send handles in lowercase, while saved ones keep whatever case they were typed in.
+    pub fn apply(&mut self, (handle, (status, stream)): &StatusUpdate) -> bool {
+        if !self.handle.eq_ignore_ascii_case(handle) {
+            return false;
         }
+
+        self.status = status.clone();
+        self.stream = stream.clone();
+
+        true
     }
 
-    // TODO add support for currently hosting?
+    #[must_use]
+    pub fn favourites_file() -> PathBuf {
+        paths::data_file(FAVOURITES_FILE)
+    }
 
-    // TODO popup if channel is offline (are you sure?)
-    #[allow(clippy::missing_errors_doc)]
-    pub fn launch(&self) -> Result<Output> {
-        Command::new("powershell")
-            .arg("Start-Process")
-            .arg("streamlink")
-            .arg(format!("twitch.tv/{}", self.handle))
-            .arg("-WindowStyle")
-            .arg("Hidden")
-            .output()
+    /// Every favourite and list channel, each once, favourites first. A channel asks to be
+    /// notified if it does anywhere it is saved.
+    #[must_use]
+    pub fn tracked() -> Vec<Self> {
+        let favourites = Self::load_from_file(&Self::favourites_file()).unwrap_or_default();
+
+        let mut channels: Vec<Self> = Vec::new();
+
+        for channel in favourites
+            .into_iter()
+            .chain(List::load_all().into_iter().flat_map(|list| list.channels))
+        {
+            match channels
+                .iter_mut()
+                .find(|tracked| tracked.handle.eq_ignore_ascii_case(&channel.handle))
+            {
+                Some(tracked) => tracked.notify |= channel.notify,
+                None => channels.push(channel),
+            }
+        }
+
+        channels
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn launch_chat(&self) -> Result<Output> {
-        Command::new("powershell")
-            .arg("Start-Process")
-            .arg("\"C:\\ProgramData\\Microsoft\\Windows\\Start Menu\\Programs\\Chatterino\"")
-            .arg(format!("\"-c {}\"", self.handle))
-            .output()
+    pub fn load_from_file(file: &Path) -> Result<Vec<Self>> {
+        let contents: ListFile = storage::load(file)?;
+
+        Ok(contents.channels)
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn load_from_file(file: &str) -> Result<Vec<Self>> {
-        let data: String = read_to_string(file)?;
-
-        Ok(serde_json::from_str(data.as_str())?)
+    pub fn save_to_file(file: &Path, channels: &[Self]) -> Result<()> {
+        storage::save(
+            file,
+            &ListFile {
+                player: None,
+                channels: channels.to_vec(),
+            },
+        )
     }
 
+    /// Answers from `statuses` where it is fresh and fetches the rest, storing what comes back.
     pub fn check(
         channels: &[Self],
         account: &Account,
-        sender: &UnboundedSender<(String, (Status, Option<String>))>,
+        statuses: &StatusCache,
+        sender: &UnboundedSender<StatusUpdate>,
     ) {
-        // TODO add cache of statuses?
-        for channel in channels {
-            let tx = (*sender).clone();
-
-            let channel_handle: String = channel.handle.clone();
-            let secret: Secret = account.access_token();
-
-            spawn(async move {
-                tx.send((
-                    String::from(&channel_handle),
-                    Self::update_status_and_game(channel_handle, secret).await,
-                ))
-            });
+        let all_handles: Vec<String> = channels
+            .iter()
+            .map(|channel| channel.handle.clone())
+            .collect();
+
+        let (cached, handles) = statuses.split(&all_handles);
+
+        for update in cached {
+            let _result = sender.send(update);
         }
+
+        if handles.is_empty() {
+            return;
+        }
+
+        let tx = (*sender).clone();
+        let secret: Secret = account.access_token();
+        let statuses = statuses.clone();
+
+        spawn(async move {
+            let (throttle_tx, mut throttle_rx) = unbounded_channel::<Duration>();
+
+            let check = async {
+                match StatusService::new(&secret, statuses.rate_limit(), Some(throttle_tx)).await {
+                    Ok(service) => service.check(&handles).await,
+                    Err(e) => {
+                        error!("Could not check channel statuses: {e}");
+                        status_service::unknown(&handles)
+                    }
+                }
+            };
+            tokio::pin!(check);
+
+            // Show the channels as throttled while requests are held back
+            let updates = loop {
+                tokio::select! {
+                    updates = &mut check => break updates,
+                    Some(_) = throttle_rx.recv() => {
+                        for update in status_service::throttled(&handles) {
+                            let _result = tx.send(update);
+                        }
+                    }
+                }
+            };
+
+            statuses.store(&updates);
+
+            for update in updates {
+                let _result = tx.send(update);
+            }
+        });
     }
 }
 
-// TODO consider using other format for storing these? e.g. TOML/YAML (easier to type manually but this should be generated by the program)
-
 // TODO need to add account configuration
 // TODO github actions to check code?
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(handle: &str) -> StatusUpdate {
+        let stream = StreamInfo {
+            title: String::from("Title"),
+            ..StreamInfo::default()
+        };
+
+        (handle.to_owned(), (Status::Online, Some(stream)))
+    }
+
+    #[test]
+    fn mixed_case_handle_takes_the_update() {
+        let mut channel = Channel::new(String::from("Shroud"), String::from("Shroud"));
+
+        assert!(channel.apply(&live("shroud")));
+        assert!(channel.status == Status::Online);
+        assert_eq!(
+            channel.stream.map(|stream| stream.title).as_deref(),
+            Some("Title")
+        );
+    }
+
+    #[test]
+    fn other_channels_are_left_alone() {
+        let mut channel = Channel::new(String::from("Shroud"), String::from("Shroud"));
+
+        assert!(!channel.apply(&live("summit1g")));
+        assert!(channel.status == Status::default());
+        assert!(channel.stream.is_none());
+    }
+}
@@ -7,6 +7,7 @@ pub enum Status {
     Awaiting,
     Online,
     Offline,
+    Throttled,
     Unknown,
 }
 
@@ -23,6 +24,7 @@ impl Status {
             Self::Awaiting => "...  ",
             Self::Online => "online",
             Self::Offline => "offline",
+            Self::Throttled => "throttled",
             Self::Unknown => "unknown",
         }
     }
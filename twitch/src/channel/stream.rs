@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const THUMBNAIL_WIDTH_PLACEHOLDER: &str = "{width}";
+const THUMBNAIL_HEIGHT_PLACEHOLDER: &str = "{height}";
+
+/// What Helix says about a live stream.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct StreamInfo {
+    pub game: String,
+    pub title: String,
+    // Not known for a stream that was only just announced
+    pub viewers: Option<usize>,
+    pub started_at: Option<SystemTime>,
+    pub tags: Vec<String>,
+    pub language: String,
+    pub mature: bool,
+    pub thumbnail_url: String,
+}
+
+impl StreamInfo {
+    #[must_use]
+    pub fn uptime(&self) -> Option<Duration> {
+        self.started_at?.elapsed().ok()
+    }
+
+    #[must_use]
+    pub fn thumbnail(&self, width: u32, height: u32) -> String {
+        self.thumbnail_url
+            .replace(THUMBNAIL_WIDTH_PLACEHOLDER, &width.to_string())
+            .replace(THUMBNAIL_HEIGHT_PLACEHOLDER, &height.to_string())
+    }
+}
+
+/// Parses the UTC timestamps Helix sends, e.g. `2021-03-10T15:04:21Z`.
+#[must_use]
+pub fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let (date, time) = timestamp.trim_end_matches('Z').split_once('T')?;
+
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let year = date_parts.next()?.ok()?;
+    let month = date_parts.next()?.ok()?;
+    let day = date_parts.next()?.ok()?;
+
+    let mut time_parts = time.splitn(3, ':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    // Drop fractional seconds
+    let second: i64 = time_parts.next()?.split('.').next()?.parse().ok()?;
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+
+    u64::try_from(seconds)
+        .ok()
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+// Days since 1970-01-01 for a date in the proleptic Gregorian calendar
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: u64) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn epoch() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(UNIX_EPOCH));
+    }
+
+    #[test]
+    fn helix_timestamp() {
+        assert_eq!(parse_timestamp("2021-03-10T15:04:21Z"), at(1_615_388_661));
+    }
+
+    #[test]
+    fn leap_day() {
+        assert_eq!(parse_timestamp("2020-02-29T00:00:00Z"), at(1_582_934_400));
+        assert_eq!(parse_timestamp("2024-02-29T23:59:59Z"), at(1_709_251_199));
+    }
+
+    #[test]
+    fn fractional_seconds_are_dropped() {
+        assert_eq!(
+            parse_timestamp("2021-03-10T15:04:21.123456Z"),
+            at(1_615_388_661)
+        );
+    }
+
+    #[test]
+    fn malformed_is_none() {
+        for timestamp in [
+            "",
+            "not a timestamp",
+            "2021-03-10",
+            "2021-03-10T15:04Z",
+            "2021-03-xxT15:04:21Z",
+            "1969-12-31T23:59:59Z",
+        ] {
+            assert_eq!(parse_timestamp(timestamp), None, "{timestamp}");
+        }
+    }
+}
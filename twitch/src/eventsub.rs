@@ -0,0 +1,872 @@
+use crate::{
+    account::Account, channel::stream::parse_timestamp, request::RateLimit, secret::Secret,
+    status_service::StatusService,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::StreamExt;
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Method, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    net::TcpStream,
+    spawn,
+    sync::{mpsc::UnboundedSender, watch},
+    time::{sleep, timeout, timeout_at, Instant},
+};
+use tokio_tungstenite::{connect_async, tungstenite, MaybeTlsStream, WebSocketStream};
+use tracing::{debug, info, warn};
+use twitch_api::{
+    client::{Bytes, ResponseExt},
+    helix::{Request, RequestGet},
+    twitch_oauth2::{Scope, TwitchToken},
+    HttpClient,
+};
+
+pub const WEBSOCKET_URL: &str = "wss://eventsub.wss.twitch.tv/ws";
+pub const SUBSCRIPTIONS_URL: &str = "https://api.twitch.tv/helix/eventsub/subscriptions";
+
+// Subscription types and the version of each that is asked for
+const SUBSCRIPTIONS: [(&str, &str); 3] = [
+    (STREAM_ONLINE, "1"),
+    (STREAM_OFFLINE, "1"),
+    (CHANNEL_UPDATE, "2"),
+];
+const STREAM_ONLINE: &str = "stream.online";
+const STREAM_OFFLINE: &str = "stream.offline";
+const CHANNEL_UPDATE: &str = "channel.update";
+
+// Helix accepts up to 100 logins per Get Users request
+const BATCH_SIZE: usize = 100;
+
+// How long Twitch goes quiet before sending a keepalive, unless the welcome says otherwise
+const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(10);
+// Leeway before a missing keepalive is taken as a dead connection
+const KEEPALIVE_GRACE: Duration = Duration::from_secs(5);
+
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_WAIT: Duration = Duration::from_secs(60);
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// What EventSub says happened to a channel.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum StreamEvent {
+    Online {
+        handle: String,
+        started_at: Option<SystemTime>,
+    },
+    Offline {
+        handle: String,
+    },
+    Updated {
+        handle: String,
+        title: String,
+        game: String,
+    },
+}
+
+impl StreamEvent {
+    #[must_use]
+    pub fn handle(&self) -> &str {
+        match self {
+            Self::Online { handle, .. }
+            | Self::Offline { handle }
+            | Self::Updated { handle, .. } => handle,
+        }
+    }
+}
+
+/// Where the session and its subscriptions are made. Pointing both at the Twitch CLI's mock
+/// server (`twitch event websocket start-server`) or a local stub is how this is tried out.
+#[derive(Clone)]
+pub struct Endpoints {
+    pub websocket: String,
+    pub subscriptions: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            websocket: String::from(WEBSOCKET_URL),
+            subscriptions: String::from(SUBSCRIPTIONS_URL),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Message {
+    metadata: Metadata,
+    #[serde(default)]
+    payload: Payload,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    message_type: String,
+}
+
+#[derive(Deserialize, Default)]
+struct Payload {
+    session: Option<Session>,
+    subscription: Option<Subscription>,
+    event: Option<Event>,
+}
+
+#[derive(Deserialize)]
+struct Session {
+    id: String,
+    keepalive_timeout_seconds: Option<u64>,
+    reconnect_url: Option<String>,
+}
+
+impl Session {
+    fn keepalive(&self) -> Duration {
+        self.keepalive_timeout_seconds
+            .map_or(DEFAULT_KEEPALIVE, Duration::from_secs)
+    }
+}
+
+#[derive(Deserialize)]
+struct Subscription {
+    #[serde(rename = "type")]
+    kind: String,
+    status: String,
+}
+
+// The fields used from stream.online, stream.offline and channel.update events
+#[derive(Deserialize)]
+struct Event {
+    broadcaster_user_login: String,
+    #[serde(default)]
+    started_at: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    category_name: Option<String>,
+}
+
+impl Message {
+    fn stream_event(self) -> Option<StreamEvent> {
+        let subscription = self.payload.subscription?;
+        let event = self.payload.event?;
+        let handle = event.broadcaster_user_login;
+
+        match subscription.kind.as_str() {
+            STREAM_ONLINE => Some(StreamEvent::Online {
+                handle,
+                started_at: event.started_at.as_deref().and_then(parse_timestamp),
+            }),
+            STREAM_OFFLINE => Some(StreamEvent::Offline { handle }),
+            CHANNEL_UPDATE => Some(StreamEvent::Updated {
+                handle,
+                title: event.title.unwrap_or_default(),
+                game: event.category_name.unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CreateSubscription<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    version: &'a str,
+    condition: Condition<'a>,
+    transport: Transport<'a>,
+}
+
+#[derive(Serialize)]
+struct Condition<'a> {
+    broadcaster_user_id: &'a str,
+}
+
+#[derive(Serialize)]
+struct Transport<'a> {
+    method: &'a str,
+    session_id: &'a str,
+}
+
+// Get Users, only for the IDs subscriptions are made with
+#[derive(Serialize)]
+struct GetUsers<'a> {
+    login: &'a [String],
+}
+
+impl Request for GetUsers<'_> {
+    const PATH: &'static str = "users";
+    const SCOPE: &'static [Scope] = &[];
+
+    type Response = Vec<User>;
+}
+
+impl RequestGet for GetUsers<'_> {}
+
+#[derive(Deserialize, PartialEq, Eq)]
+struct User {
+    id: String,
+    login: String,
+}
+
+/// Opens an EventSub session in the background and subscribes to the stream and channel updates
+/// of every handle in `tracked`, following it as it changes and sending what happens to `sender`.
+/// Dropped connections are opened again until the receiving end is gone.
+pub fn listen(
+    endpoints: Endpoints,
+    account: &Account,
+    rate_limit: &RateLimit,
+    mut tracked: watch::Receiver<Vec<String>>,
+    sender: UnboundedSender<StreamEvent>,
+) {
+    let secret: Secret = account.access_token();
+    let rate_limit = rate_limit.clone();
+
+    spawn(async move {
+        let service = match StatusService::new(&secret, &rate_limit, None).await {
+            Ok(service) => service,
+            Err(e) => {
+                warn!("Could not start EventSub: {e:#}");
+                return;
+            }
+        };
+
+        let mut attempt: u32 = 0;
+
+        loop {
+            // Twitch closes sessions that aren't subscribed to anything, so wait for a channel
+            while tracked.borrow().is_empty() {
+                if tracked.changed().await.is_err() {
+                    return;
+                }
+            }
+
+            match session(&endpoints, &service, &mut tracked, &sender, &mut attempt).await {
+                Ok(()) => return,
+                Err(e) => warn!("EventSub connection lost: {e:#}"),
+            }
+
+            if sender.is_closed() {
+                return;
+            }
+
+            sleep(reconnect_wait(attempt)).await;
+
+            attempt = attempt.saturating_add(1);
+        }
+    });
+}
+
+/// What to do about a message received during a session.
+#[derive(PartialEq, Eq, Debug)]
+enum Step {
+    Continue,
+    Send(StreamEvent),
+    Reconnect(String),
+}
+
+impl Message {
+    fn step(self) -> Result<Step> {
+        match self.metadata.message_type.as_str() {
+            "session_keepalive" => Ok(Step::Continue),
+            "notification" => Ok(self.stream_event().map_or(Step::Continue, Step::Send)),
+            "session_reconnect" => self
+                .payload
+                .session
+                .and_then(|session| session.reconnect_url)
+                .map(Step::Reconnect)
+                .ok_or_else(|| anyhow!("Asked to reconnect without a URL")),
+            "revocation" => {
+                if let Some(subscription) = self.payload.subscription {
+                    warn!(
+                        "Twitch revoked a {} subscription: {}",
+                        subscription.kind, subscription.status
+                    );
+                }
+
+                Ok(Step::Continue)
+            }
+            other => {
+                debug!("Ignoring EventSub {other} message");
+
+                Ok(Step::Continue)
+            }
+        }
+    }
+}
+
+// Runs one session until the connection is lost, or returns Ok once nobody is listening anymore
+async fn session(
+    endpoints: &Endpoints,
+    service: &StatusService,
+    tracked: &mut watch::Receiver<Vec<String>>,
+    sender: &UnboundedSender<StreamEvent>,
+    attempt: &mut u32,
+) -> Result<()> {
+    let (mut socket, welcome) = connect(&endpoints.websocket).await?;
+
+    *attempt = 0;
+
+    info!("EventSub session {} started", welcome.id);
+
+    // A new session starts without subscriptions, so every handle is looked up again
+    let mut subscriptions = Subscriptions::new(&endpoints.subscriptions, welcome.id.clone());
+    let handles = tracked.borrow_and_update().clone();
+    subscriptions.sync(service, &handles).await;
+
+    let mut keepalive = welcome.keepalive();
+    let mut deadline = Instant::now() + keepalive + KEEPALIVE_GRACE;
+
+    loop {
+        let message = tokio::select! {
+            message = timeout_at(deadline, next_message(&mut socket)) => message
+                .map_err(|_| anyhow!("Nothing heard from Twitch in {}s", keepalive.as_secs()))??,
+            changed = tracked.changed() => {
+                if changed.is_err() {
+                    return Ok(());
+                }
+
+                let handles = tracked.borrow_and_update().clone();
+                subscriptions.sync(service, &handles).await;
+
+                continue;
+            }
+        };
+
+        deadline = Instant::now() + keepalive + KEEPALIVE_GRACE;
+
+        match message.step()? {
+            Step::Continue => {}
+            Step::Send(event) => {
+                if sender.send(event).is_err() {
+                    return Ok(());
+                }
+            }
+            Step::Reconnect(url) => {
+                // Subscriptions move over to the new connection, the old one closes once it's up
+                let (new_socket, welcome) = connect(&url).await?;
+                let _result = socket.close(None).await;
+
+                socket = new_socket;
+                keepalive = welcome.keepalive();
+                deadline = Instant::now() + keepalive + KEEPALIVE_GRACE;
+
+                debug!("EventSub session {} reconnected", welcome.id);
+            }
+        }
+    }
+}
+
+// Connects and waits for the welcome, which is always the first message
+async fn connect(url: &str) -> Result<(Socket, Session)> {
+    let (mut socket, _) = connect_async(url)
+        .await
+        .with_context(|| format!("Could not connect to {url}"))?;
+
+    let message = timeout(DEFAULT_KEEPALIVE, next_message(&mut socket))
+        .await
+        .map_err(|_| anyhow!("No welcome from {url}"))??;
+
+    match (
+        message.metadata.message_type.as_str(),
+        message.payload.session,
+    ) {
+        ("session_welcome", Some(session)) => Ok((socket, session)),
+        (other, _) => bail!("Expected a welcome from {url}, got {other}"),
+    }
+}
+
+async fn next_message(socket: &mut Socket) -> Result<Message> {
+    loop {
+        match socket.next().await {
+            Some(Ok(tungstenite::Message::Text(text))) => {
+                return serde_json::from_str(&text)
+                    .with_context(|| "Could not read an EventSub message");
+            }
+            Some(Ok(tungstenite::Message::Close(frame))) => {
+                bail!(
+                    "Closed by Twitch: {}",
+                    frame.map_or_else(String::new, |frame| frame.reason.into_owned())
+                )
+            }
+            // Pings are answered by tungstenite itself
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+            None => bail!("Connection closed"),
+        }
+    }
+}
+
+// What a session is subscribed to, the subscription IDs of each handle
+struct Subscriptions<'a> {
+    url: &'a str,
+    session_id: String,
+    by_handle: HashMap<String, Vec<String>>,
+    // Twitch took no more subscriptions, nothing is tried again until some are removed
+    full: bool,
+}
+
+impl<'a> Subscriptions<'a> {
+    fn new(url: &'a str, session_id: String) -> Self {
+        Self {
+            url,
+            session_id,
+            by_handle: HashMap::new(),
+            full: false,
+        }
+    }
+
+    /// Subscribes to the handles that are new and removes the subscriptions of those that are
+    /// gone. A subscription that can't be made leaves its channel to the regular status checks.
+    async fn sync(&mut self, service: &StatusService, handles: &[String]) {
+        let mut wanted: Vec<String> = handles.iter().map(|handle| key(handle)).collect();
+        wanted.sort();
+        wanted.dedup();
+
+        let removed: Vec<String> = self
+            .by_handle
+            .keys()
+            .filter(|handle| wanted.binary_search(handle).is_err())
+            .cloned()
+            .collect();
+
+        for handle in removed {
+            for id in self.by_handle.remove(&handle).unwrap_or_default() {
+                if let Err(e) = delete_subscription(self.url, service, &id).await {
+                    warn!("Could not unsubscribe from {handle}: {e:#}");
+                }
+            }
+
+            self.full = false;
+        }
+
+        let added: Vec<String> = wanted
+            .into_iter()
+            .filter(|handle| !self.by_handle.contains_key(handle))
+            .collect();
+
+        if added.is_empty() || self.full {
+            return;
+        }
+
+        let users = match users(service, &added).await {
+            Ok(users) => users,
+            Err(e) => {
+                warn!("Could not subscribe to EventSub: {e:#}");
+                return;
+            }
+        };
+
+        for user in users {
+            match self.subscribe(service, &user.id).await {
+                Some(ids) => {
+                    self.by_handle.insert(key(&user.login), ids);
+                }
+                None => {
+                    warn!("Twitch won't take more EventSub subscriptions, the rest are polled");
+                    self.full = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    // Every subscription type for one channel, None once Twitch won't take any more
+    async fn subscribe(&self, service: &StatusService, user_id: &str) -> Option<Vec<String>> {
+        let mut ids: Vec<String> = Vec::with_capacity(SUBSCRIPTIONS.len());
+
+        for (kind, version) in SUBSCRIPTIONS {
+            let body = CreateSubscription {
+                kind,
+                version,
+                condition: Condition {
+                    broadcaster_user_id: user_id,
+                },
+                transport: Transport {
+                    method: "websocket",
+                    session_id: &self.session_id,
+                },
+            };
+
+            match create_subscription(self.url, service, &body).await {
+                Ok(Created::Id(id)) => ids.push(id),
+                Ok(Created::Exists) => {}
+                Ok(Created::Full) => return None,
+                Err(e) => warn!("Could not subscribe to {kind} for {user_id}: {e:#}"),
+            }
+        }
+
+        Some(ids)
+    }
+}
+
+enum Created {
+    Id(String),
+    Exists,
+    Full,
+}
+
+#[derive(Deserialize)]
+struct CreatedSubscriptions {
+    data: Vec<CreatedSubscription>,
+}
+
+#[derive(Deserialize)]
+struct CreatedSubscription {
+    id: String,
+}
+
+async fn users(service: &StatusService, handles: &[String]) -> Result<Vec<User>> {
+    let client = service.helix();
+
+    let mut users: Vec<User> = Vec::with_capacity(handles.len());
+
+    for batch in handles.chunks(BATCH_SIZE) {
+        let response = client
+            .req_get(GetUsers { login: batch }, service.token())
+            .await
+            .with_context(|| "Could not look up channel IDs")?;
+
+        users.extend(response.data);
+    }
+
+    Ok(users)
+}
+
+async fn create_subscription(
+    url: &str,
+    service: &StatusService,
+    body: &CreateSubscription<'_>,
+) -> Result<Created> {
+    let request = authorized(service, Method::POST, url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Bytes::from(serde_json::to_vec(body)?))?;
+
+    let response = service
+        .client()
+        .req(request)
+        .await?
+        .into_response_vec()
+        .await?;
+
+    match response.status() {
+        StatusCode::ACCEPTED => {
+            let created: CreatedSubscriptions = serde_json::from_slice(response.body())?;
+
+            created
+                .data
+                .into_iter()
+                .next()
+                .map(|subscription| Created::Id(subscription.id))
+                .ok_or_else(|| anyhow!("Twitch didn't say which subscription was made"))
+        }
+        StatusCode::CONFLICT => Ok(Created::Exists),
+        // Out of subscriptions for this session, the rest would fail the same way
+        StatusCode::TOO_MANY_REQUESTS => Ok(Created::Full),
+        status => bail!("Twitch answered {status}"),
+    }
+}
+
+async fn delete_subscription(url: &str, service: &StatusService, id: &str) -> Result<()> {
+    let request =
+        authorized(service, Method::DELETE, &format!("{url}?id={id}")).body(Bytes::new())?;
+
+    let response = service.client().req(request).await?;
+
+    match response.status() {
+        StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(()),
+        status => bail!("Twitch answered {status}"),
+    }
+}
+
+fn authorized(service: &StatusService, method: Method, url: &str) -> http::request::Builder {
+    let token = service.token();
+
+    http::Request::builder()
+        .method(method)
+        .uri(url)
+        .header(AUTHORIZATION, format!("Bearer {}", token.token().secret()))
+        .header("Client-Id", token.client_id().as_str())
+}
+
+// Handles are case-insensitive on Twitch
+fn key(handle: &str) -> String {
+    handle.to_ascii_lowercase()
+}
+
+fn reconnect_wait(attempt: u32) -> Duration {
+    (RECONNECT_BASE * 2_u32.saturating_pow(attempt.min(6))).min(MAX_RECONNECT_WAIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::SinkExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    const WELCOME: &str = r#"{
+        "metadata": {
+            "message_id": "96a3f3b5-5dec-4eed-908e-e11ee657416c",
+            "message_type": "session_welcome",
+            "message_timestamp": "2023-07-19T14:56:51.634234626Z"
+        },
+        "payload": {
+            "session": {
+                "id": "AQoQILE98gtqShGmLD7AM6yJThAB",
+                "status": "connected",
+                "connected_at": "2023-07-19T14:56:51.616329898Z",
+                "keepalive_timeout_seconds": 30,
+                "reconnect_url": null
+            }
+        }
+    }"#;
+
+    fn message(json: &str) -> Message {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn notification(kind: &str, event: &str) -> Message {
+        message(&format!(
+            r#"{{
+                "metadata": {{
+                    "message_id": "befa7b53-d79d-478f-86b9-120f112b044e",
+                    "message_type": "notification",
+                    "message_timestamp": "2022-11-16T10:11:12.464757833Z",
+                    "subscription_type": "{kind}",
+                    "subscription_version": "1"
+                }},
+                "payload": {{
+                    "subscription": {{
+                        "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+                        "status": "enabled",
+                        "type": "{kind}",
+                        "version": "1",
+                        "cost": 1,
+                        "condition": {{ "broadcaster_user_id": "1337" }},
+                        "transport": {{ "method": "websocket", "session_id": "AQoQexAWVYKSTIu4ec_2VAxyuhAB" }},
+                        "created_at": "2022-11-16T10:11:12.464757833Z"
+                    }},
+                    "event": {event}
+                }}
+            }}"#
+        ))
+    }
+
+    #[test]
+    fn welcome_gives_the_session() {
+        let session = message(WELCOME).payload.session.unwrap();
+
+        assert_eq!(session.id, "AQoQILE98gtqShGmLD7AM6yJThAB");
+        assert_eq!(session.keepalive(), Duration::from_secs(30));
+        assert_eq!(session.reconnect_url, None);
+    }
+
+    #[test]
+    fn keepalive_continues() {
+        let keepalive = message(
+            r#"{
+                "metadata": {
+                    "message_id": "84c1e79a-2a4b-4c13-ba0b-4312293e9308",
+                    "message_type": "session_keepalive",
+                    "message_timestamp": "2023-07-19T10:11:12.634234626Z"
+                },
+                "payload": {}
+            }"#,
+        );
+
+        assert_eq!(keepalive.step().unwrap(), Step::Continue);
+    }
+
+    #[test]
+    fn stream_online_is_sent() {
+        let online = notification(
+            STREAM_ONLINE,
+            r#"{
+                "id": "9001",
+                "broadcaster_user_id": "1337",
+                "broadcaster_user_login": "cool_user",
+                "broadcaster_user_name": "Cool_User",
+                "type": "live",
+                "started_at": "2020-10-11T10:11:12.123Z"
+            }"#,
+        );
+
+        assert_eq!(
+            online.step().unwrap(),
+            Step::Send(StreamEvent::Online {
+                handle: String::from("cool_user"),
+                started_at: parse_timestamp("2020-10-11T10:11:12.123Z"),
+            })
+        );
+    }
+
+    #[test]
+    fn stream_offline_is_sent() {
+        let offline = notification(
+            STREAM_OFFLINE,
+            r#"{
+                "broadcaster_user_id": "1337",
+                "broadcaster_user_login": "cool_user",
+                "broadcaster_user_name": "Cool_User"
+            }"#,
+        );
+
+        assert_eq!(
+            offline.step().unwrap(),
+            Step::Send(StreamEvent::Offline {
+                handle: String::from("cool_user"),
+            })
+        );
+    }
+
+    #[test]
+    fn channel_update_is_sent() {
+        let update = notification(
+            CHANNEL_UPDATE,
+            r#"{
+                "broadcaster_user_id": "1337",
+                "broadcaster_user_login": "cool_user",
+                "broadcaster_user_name": "Cool_User",
+                "title": "Best Stream Ever",
+                "language": "en",
+                "category_id": "12453",
+                "category_name": "Grand Theft Auto",
+                "content_classification_labels": []
+            }"#,
+        );
+
+        assert_eq!(
+            update.step().unwrap(),
+            Step::Send(StreamEvent::Updated {
+                handle: String::from("cool_user"),
+                title: String::from("Best Stream Ever"),
+                game: String::from("Grand Theft Auto"),
+            })
+        );
+    }
+
+    #[test]
+    fn other_notifications_continue() {
+        let follow = notification(
+            "channel.follow",
+            r#"{
+                "user_id": "1234",
+                "user_login": "cool_user",
+                "broadcaster_user_id": "1337",
+                "broadcaster_user_login": "cooler_user"
+            }"#,
+        );
+
+        assert_eq!(follow.step().unwrap(), Step::Continue);
+    }
+
+    #[test]
+    fn reconnect_gives_the_url() {
+        let reconnect = message(
+            r#"{
+                "metadata": {
+                    "message_id": "84c1e79a-2a4b-4c13-ba0b-4312293e9308",
+                    "message_type": "session_reconnect",
+                    "message_timestamp": "2022-11-18T09:10:11.634234626Z"
+                },
+                "payload": {
+                    "session": {
+                        "id": "AQoQexAWVYKSTIu4ec_2VAxyuhAB",
+                        "status": "reconnecting",
+                        "keepalive_timeout_seconds": null,
+                        "reconnect_url": "wss://eventsub.wss.twitch.tv?...",
+                        "connected_at": "2022-11-16T10:11:12.634234626Z"
+                    }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            reconnect.step().unwrap(),
+            Step::Reconnect(String::from("wss://eventsub.wss.twitch.tv?..."))
+        );
+    }
+
+    #[test]
+    fn reconnect_without_url_fails() {
+        let reconnect = message(
+            r#"{
+                "metadata": { "message_type": "session_reconnect" },
+                "payload": { "session": { "id": "AQoQexAWVYKSTIu4ec_2VAxyuhAB" } }
+            }"#,
+        );
+
+        assert!(reconnect.step().is_err());
+    }
+
+    #[test]
+    fn reconnect_wait_backs_off_up_to_the_cap() {
+        assert_eq!(reconnect_wait(0), Duration::from_secs(1));
+        assert_eq!(reconnect_wait(1), Duration::from_secs(2));
+        assert_eq!(reconnect_wait(5), Duration::from_secs(32));
+        assert_eq!(reconnect_wait(6), MAX_RECONNECT_WAIT);
+        assert_eq!(reconnect_wait(u32::MAX), MAX_RECONNECT_WAIT);
+    }
+
+    // A stub server that sends `messages` to the first connection and then keeps it open
+    async fn stub(messages: Vec<String>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(stream).await.unwrap();
+
+            for message in messages {
+                socket
+                    .send(tungstenite::Message::Text(message))
+                    .await
+                    .unwrap();
+            }
+
+            while socket.next().await.is_some() {}
+        });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn connect_waits_for_the_welcome() {
+        let url = stub(vec![String::from(WELCOME)]).await;
+
+        let (_socket, session) = connect(&url).await.unwrap();
+
+        assert_eq!(session.id, "AQoQILE98gtqShGmLD7AM6yJThAB");
+    }
+
+    #[tokio::test]
+    async fn connect_fails_without_a_welcome() {
+        let url = stub(vec![String::from(
+            r#"{ "metadata": { "message_type": "session_keepalive" }, "payload": {} }"#,
+        )])
+        .await;
+
+        assert!(connect(&url).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_connection_times_out() {
+        let url = stub(vec![String::from(WELCOME)]).await;
+
+        let (mut socket, _) = connect(&url).await.unwrap();
+
+        let received = timeout(
+            DEFAULT_KEEPALIVE + KEEPALIVE_GRACE,
+            next_message(&mut socket),
+        )
+        .await;
+
+        assert!(received.is_err());
+    }
+}
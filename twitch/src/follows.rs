@@ -0,0 +1,191 @@
+use crate::{
+    account::Account,
+    channel::{status::Status, stream::StreamInfo, Channel},
+    status_cache::StatusCache,
+    status_service::{SharedService, StatusService, Stream},
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use tokio::{spawn, sync::mpsc::UnboundedSender};
+use twitch_api::{
+    helix::{Request, RequestGet},
+    twitch_oauth2::{Scope, TwitchToken},
+};
+
+// Most Helix allows per page
+const PAGE_SIZE: usize = 100;
+
+pub type FollowsUpdate = std::result::Result<Vec<Channel>, String>;
+
+// Get Followed Channels and Get Followed Streams, neither of which this twitch_api version knows
+#[derive(Serialize)]
+struct GetFollowedChannels<'a> {
+    user_id: &'a str,
+    first: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+impl Request for GetFollowedChannels<'_> {
+    const PATH: &'static str = "channels/followed";
+    const SCOPE: &'static [Scope] = &[Scope::UserReadFollows];
+
+    type Response = Vec<FollowedChannel>;
+}
+
+impl RequestGet for GetFollowedChannels<'_> {}
+
+#[derive(Deserialize, PartialEq, Eq)]
+struct FollowedChannel {
+    broadcaster_login: String,
+    broadcaster_name: String,
+}
+
+#[derive(Serialize)]
+struct GetFollowedStreams<'a> {
+    user_id: &'a str,
+    first: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+impl Request for GetFollowedStreams<'_> {
+    const PATH: &'static str = "streams/followed";
+    const SCOPE: &'static [Scope] = &[Scope::UserReadFollows];
+
+    type Response = Vec<Stream>;
+}
+
+impl RequestGet for GetFollowedStreams<'_> {}
+
+/// Every channel the account follows, live ones first (by viewers) and then the rest by name.
+#[allow(clippy::missing_errors_doc)]
+pub async fn followed(service: &StatusService, account: &Account) -> Result<Vec<Channel>> {
+    // Logins from before the Follows tab weren't asked for it
+    if !service.token().scopes().contains(&Scope::UserReadFollows) {
+        bail!(
+            "This login can't read followed channels, delete account.toml and log in again to \
+             allow it"
+        );
+    }
+
+    let client = service.helix();
+    let user_id = account.user_id();
+
+    let mut streams: Vec<Stream> = Vec::new();
+    let mut after: Option<String> = None;
+
+    loop {
+        let req = GetFollowedStreams {
+            user_id,
+            first: PAGE_SIZE,
+            after: after.take(),
+        };
+
+        let response = client
+            .req_get(req, service.token())
+            .await
+            .with_context(|| "Could not get followed streams")?;
+
+        streams.extend(response.data);
+
+        match response.pagination {
+            Some(cursor) if !cursor.as_str().is_empty() => after = Some(cursor.to_string()),
+            _ => break,
+        }
+    }
+
+    let mut followed: Vec<FollowedChannel> = Vec::new();
+
+    loop {
+        let req = GetFollowedChannels {
+            user_id,
+            first: PAGE_SIZE,
+            after: after.take(),
+        };
+
+        let response = client
+            .req_get(req, service.token())
+            .await
+            .with_context(|| "Could not get followed channels")?;
+
+        followed.extend(response.data);
+
+        match response.pagination {
+            Some(cursor) if !cursor.as_str().is_empty() => after = Some(cursor.to_string()),
+            _ => break,
+        }
+    }
+
+    streams.sort_by_key(|stream| Reverse(stream.viewer_count));
+
+    let mut channels: Vec<Channel> = streams
+        .iter()
+        .map(|stream| {
+            let mut channel = Channel::new(stream.user_name.clone(), stream.user_login.clone());
+            channel.status = Status::Online;
+            channel.stream = Some(StreamInfo::from(stream));
+            channel
+        })
+        .collect();
+
+    followed.sort_by_key(|channel| channel.broadcaster_name.to_lowercase());
+
+    for followed_channel in followed {
+        if channels
+            .iter()
+            .any(|channel| channel.handle == followed_channel.broadcaster_login)
+        {
+            continue;
+        }
+
+        let mut channel = Channel::new(
+            followed_channel.broadcaster_name,
+            followed_channel.broadcaster_login,
+        );
+        channel.status = Status::Offline;
+        channels.push(channel);
+    }
+
+    Ok(channels)
+}
+
+/// Fetches the followed channels in the background, keeping `statuses` up to date with them. The
+/// token `service` holds is reused, so refreshing doesn't validate it again.
+pub fn fetch(
+    account: &Account,
+    service: &SharedService,
+    statuses: &StatusCache,
+    sender: &UnboundedSender<FollowsUpdate>,
+) {
+    let account = account.clone();
+    let service = service.clone();
+    let statuses = statuses.clone();
+    let tx = (*sender).clone();
+
+    spawn(async move {
+        let channels = async {
+            let validated = service.get(&account, statuses.rate_limit()).await?;
+
+            followed(&validated, &account).await
+        }
+        .await;
+
+        let update = match channels {
+            Ok(channels) => {
+                statuses.store_channels(&channels);
+
+                Ok(channels)
+            }
+            Err(e) => {
+                // The token may be why it failed, so it is validated again next time
+                service.forget().await;
+
+                Err(format!("{e:#}"))
+            }
+        };
+
+        let _result = tx.send(update);
+    });
+}
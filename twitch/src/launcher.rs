@@ -0,0 +1,104 @@
+#[cfg(all(unix, not(target_os = "macos")))]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub use linux::Linux as Native;
+#[cfg(target_os = "macos")]
+pub use macos::MacOs as Native;
+#[cfg(windows)]
+pub use windows::Windows as Native;
+
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, ErrorKind},
+    process::{Child, Command, Stdio},
+};
+
+pub trait Launcher {
+    /// Spawns `program` detached from the terminal, without going through a shell.
+    #[allow(clippy::missing_errors_doc)]
+    fn spawn(&self, program: &str, args: &[String]) -> Result<(), Error>;
+
+    /// Program and arguments used to open the chat for the given channel handle.
+    fn chat_command(&self, handle: &str) -> (String, Vec<String>);
+
+    #[allow(clippy::missing_errors_doc)]
+    fn launch_chat(&self, handle: &str) -> Result<(), Error> {
+        let (program, args) = self.chat_command(handle);
+
+        self.spawn(program.as_str(), &args)
+    }
+}
+
+#[must_use]
+pub const fn native() -> Native {
+    Native
+}
+
+#[derive(Debug)]
+pub enum Error {
+    EmptyCommand(String),
+    NotFound(String),
+    PermissionDenied(String),
+    Spawn(String, io::Error),
+}
+
+impl Error {
+    #[must_use]
+    pub fn from_io(program: &str, error: io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => Self::NotFound(program.to_owned()),
+            ErrorKind::PermissionDenied => Self::PermissionDenied(program.to_owned()),
+            _ => Self::Spawn(program.to_owned(), error),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyCommand(player) => write!(f, "Player {player} has no command configured"),
+            Self::NotFound(program) => write!(f, "Could not find {program}, is it installed?"),
+            Self::PermissionDenied(program) => write!(f, "Not allowed to run {program}"),
+            Self::Spawn(program, e) => write!(f, "Could not start {program}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Spawn(_, e) => Some(e),
+            Self::EmptyCommand(_) | Self::NotFound(_) | Self::PermissionDenied(_) => None,
+        }
+    }
+}
+
+fn detached_command(program: &str, args: &[String]) -> Command {
+    let mut command = Command::new(program);
+
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    command
+}
+
+fn spawn_detached(program: &str, mut command: Command) -> Result<(), Error> {
+    let child = command.spawn().map_err(|e| Error::from_io(program, e))?;
+
+    reap(child);
+
+    Ok(())
+}
+
+// Children are never waited on otherwise, so wait from a thread to avoid leaving zombies behind
+fn reap(mut child: Child) {
+    std::thread::spawn(move || child.wait());
+}
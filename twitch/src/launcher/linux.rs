@@ -0,0 +1,18 @@
+use super::{detached_command, spawn_detached, Error, Launcher};
+
+const CHAT_PROGRAM: &str = "chatterino";
+
+pub struct Linux;
+
+impl Launcher for Linux {
+    fn spawn(&self, program: &str, args: &[String]) -> Result<(), Error> {
+        spawn_detached(program, detached_command(program, args))
+    }
+
+    fn chat_command(&self, handle: &str) -> (String, Vec<String>) {
+        (
+            String::from(CHAT_PROGRAM),
+            vec![String::from("-c"), handle.to_owned()],
+        )
+    }
+}
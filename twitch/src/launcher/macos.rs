@@ -0,0 +1,25 @@
+use super::{detached_command, spawn_detached, Error, Launcher};
+
+const OPEN_PROGRAM: &str = "open";
+const CHAT_APPLICATION: &str = "Chatterino";
+
+pub struct MacOs;
+
+impl Launcher for MacOs {
+    fn spawn(&self, program: &str, args: &[String]) -> Result<(), Error> {
+        spawn_detached(program, detached_command(program, args))
+    }
+
+    fn chat_command(&self, handle: &str) -> (String, Vec<String>) {
+        (
+            String::from(OPEN_PROGRAM),
+            vec![
+                String::from("-a"),
+                String::from(CHAT_APPLICATION),
+                String::from("--args"),
+                String::from("-c"),
+                handle.to_owned(),
+            ],
+        )
+    }
+}
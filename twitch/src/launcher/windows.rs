@@ -0,0 +1,32 @@
+use super::{detached_command, spawn_detached, Error, Launcher};
+use std::{env, os::windows::process::CommandExt, path::PathBuf};
+
+const DETACHED_PROCESS: u32 = 0x0000_0008;
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+
+const CHAT_PROGRAM: &str = "chatterino.exe";
+const CHAT_INSTALL_DIR: &str = "Chatterino";
+
+pub struct Windows;
+
+impl Launcher for Windows {
+    fn spawn(&self, program: &str, args: &[String]) -> Result<(), Error> {
+        let mut command = detached_command(program, args);
+
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+
+        spawn_detached(program, command)
+    }
+
+    fn chat_command(&self, handle: &str) -> (String, Vec<String>) {
+        let program = env::var_os("ProgramFiles")
+            .map(|dir| PathBuf::from(dir).join(CHAT_INSTALL_DIR).join(CHAT_PROGRAM))
+            .filter(|path| path.exists())
+            .map_or_else(
+                || String::from(CHAT_PROGRAM),
+                |path| path.to_string_lossy().into_owned(),
+            );
+
+        (program, vec![String::from("-c"), handle.to_owned()])
+    }
+}
@@ -1,4 +1,17 @@
 pub mod account;
+pub mod browse;
 pub mod channel;
+pub mod eventsub;
+pub mod follows;
+pub mod launcher;
+pub mod notifier;
+pub mod paths;
+pub mod player;
+pub mod quality;
+pub mod request;
+pub mod search;
 pub use channel::status;
 mod secret;
+pub mod status_cache;
+pub mod status_service;
+pub mod storage;
@@ -0,0 +1,226 @@
+use crate::{
+    channel::{status::Status, stream::StreamInfo, Channel},
+    status_service::StatusUpdate,
+};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// A channel that just went live, as it is shown on the desktop.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+}
+
+impl Notification {
+    #[must_use]
+    pub fn live(channel: &Channel, stream: Option<&StreamInfo>) -> Self {
+        let body = match stream {
+            Some(stream) if stream.game.is_empty() => stream.title.clone(),
+            Some(stream) => format!("{}\n{}", stream.game, stream.title),
+            None => String::new(),
+        };
+
+        Self {
+            summary: format!("{} is live", channel.friendly_name),
+            body,
+        }
+    }
+}
+
+/// Where notifications end up, the desktop or anything standing in for it.
+pub trait Notifier {
+    /// Shows `notification` without waiting on it, failures are only logged.
+    fn notify(&self, notification: Notification);
+}
+
+/// Sends notifications to the desktop's notification server, `org.freedesktop.Notifications` on
+/// the session bus.
+#[derive(Clone, Copy, Default)]
+pub struct Desktop;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl Notifier for Desktop {
+    fn notify(&self, notification: Notification) {
+        tokio::spawn(async move {
+            if let Err(e) = dbus::notify(&notification).await {
+                warn!("Could not show a notification: {e}");
+            }
+        });
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+impl Notifier for Desktop {
+    fn notify(&self, notification: Notification) {
+        warn!(
+            "Desktop notifications need D-Bus, not shown: {}",
+            notification.summary
+        );
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod dbus {
+    use super::Notification;
+    use std::collections::HashMap;
+    use zbus::{zvariant::Value, Connection, Result};
+
+    const DESTINATION: &str = "org.freedesktop.Notifications";
+    const PATH: &str = "/org/freedesktop/Notifications";
+    const INTERFACE: &str = "org.freedesktop.Notifications";
+    const APP_NAME: &str = "Twitch Launcher";
+    // Let the server decide how long it stays up
+    const DEFAULT_TIMEOUT: i32 = -1;
+
+    pub async fn notify(notification: &Notification) -> Result<()> {
+        let connection = Connection::session().await?;
+
+        // Notify(app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout)
+        connection
+            .call_method(
+                Some(DESTINATION),
+                PATH,
+                Some(INTERFACE),
+                "Notify",
+                &(
+                    APP_NAME,
+                    0_u32,
+                    "",
+                    notification.summary.as_str(),
+                    notification.body.as_str(),
+                    Vec::<&str>::new(),
+                    HashMap::<&str, Value<'_>>::new(),
+                    DEFAULT_TIMEOUT,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Remembers the last status of each watched channel to tell when one goes from offline to online.
+#[derive(Default)]
+pub struct LiveWatch {
+    last: HashMap<String, Status>,
+}
+
+impl LiveWatch {
+    /// Takes in a status update and returns a notification if `channel` just went live. Only a
+    /// channel seen offline before counts, so nothing is sent for channels already live at start.
+    pub fn update(&mut self, channel: &Channel, update: &StatusUpdate) -> Option<Notification> {
+        let (handle, (status, stream)) = update;
+
+        // Failed checks say nothing about the channel, keep what was known before
+        if !matches!(status, Status::Online | Status::Offline) {
+            return None;
+        }
+
+        let before = self
+            .last
+            .insert(handle.to_ascii_lowercase(), status.clone());
+
+        match (before, status) {
+            (Some(Status::Offline), Status::Online) if channel.notify => {
+                Some(Notification::live(channel, stream.as_ref()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recording {
+        sent: RefCell<Vec<Notification>>,
+    }
+
+    impl Notifier for Recording {
+        fn notify(&self, notification: Notification) {
+            self.sent.borrow_mut().push(notification);
+        }
+    }
+
+    fn channel() -> Channel {
+        Channel {
+            notify: true,
+            ..Channel::new(String::from("Channel"), String::from("channel"))
+        }
+    }
+
+    fn update(status: Status) -> StatusUpdate {
+        let stream = (status == Status::Online).then(|| StreamInfo {
+            title: String::from("Title"),
+            game: String::from("Game"),
+            ..StreamInfo::default()
+        });
+
+        (String::from("channel"), (status, stream))
+    }
+
+    fn watch(statuses: Vec<Status>) -> Vec<Notification> {
+        let notifier = Recording::default();
+        let mut live = LiveWatch::default();
+
+        for status in statuses {
+            if let Some(notification) = live.update(&channel(), &update(status)) {
+                notifier.notify(notification);
+            }
+        }
+
+        notifier.sent.into_inner()
+    }
+
+    #[test]
+    fn going_live_notifies() {
+        assert_eq!(
+            watch(vec![Status::Offline, Status::Online]),
+            vec![Notification {
+                summary: String::from("Channel is live"),
+                body: String::from("Game\nTitle"),
+            }]
+        );
+    }
+
+    #[test]
+    fn live_at_start_does_not_notify() {
+        assert!(watch(vec![Status::Online, Status::Online]).is_empty());
+    }
+
+    #[test]
+    fn failed_checks_are_ignored() {
+        assert_eq!(
+            watch(vec![
+                Status::Offline,
+                Status::Unknown,
+                Status::Throttled,
+                Status::Online,
+            ])
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn staying_live_notifies_once() {
+        assert_eq!(
+            watch(vec![Status::Offline, Status::Online, Status::Online]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn not_opted_in_does_not_notify() {
+        let mut live = LiveWatch::default();
+        let channel = Channel::new(String::from("Channel"), String::from("channel"));
+
+        live.update(&channel, &update(Status::Offline));
+
+        assert!(live.update(&channel, &update(Status::Online)).is_none());
+    }
+}
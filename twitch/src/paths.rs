@@ -0,0 +1,87 @@
+use std::{
+    env::var_os,
+    path::{Path, PathBuf},
+};
+use tokio::sync::OnceCell;
+
+const APP_DIR: &str = "twitch_launcher";
+
+// Given on the command line, taking precedence over everything else
+static CONFIG_DIR: OnceCell<PathBuf> = OnceCell::const_new();
+
+/// Overrides the config directory, `--config-dir` takes precedence over it.
+pub const CONFIG_DIR_ENV: &str = "TWITCH_LAUNCHER_CONFIG_DIR";
+pub const DATA_DIR_ENV: &str = "TWITCH_LAUNCHER_DATA_DIR";
+pub const CACHE_DIR_ENV: &str = "TWITCH_LAUNCHER_CACHE_DIR";
+
+/// Where the account, players, settings and theme are kept.
+#[must_use]
+pub fn config_dir() -> PathBuf {
+    CONFIG_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| resolve(CONFIG_DIR_ENV, "XDG_CONFIG_HOME", &[".config"]))
+}
+
+/// Where favourites, lists and logs are kept.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    resolve(DATA_DIR_ENV, "XDG_DATA_HOME", &[".local", "share"])
+}
+
+/// Where anything that can be thrown away is kept.
+#[must_use]
+pub fn cache_dir() -> PathBuf {
+    resolve(CACHE_DIR_ENV, "XDG_CACHE_HOME", &[".cache"])
+}
+
+#[must_use]
+pub fn config_file(name: &str) -> PathBuf {
+    config_dir().join(name)
+}
+
+#[must_use]
+pub fn data_file(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+#[must_use]
+pub fn cache_file(name: &str) -> PathBuf {
+    cache_dir().join(name)
+}
+
+/// Points the config directory somewhere else for the rest of the run. Only the first call counts.
+pub fn set_config_dir(dir: &Path) {
+    let _result = CONFIG_DIR.set(dir.to_path_buf());
+}
+
+// The app's own override, then the XDG base directory, then the XDG default under the home
+// directory. The current directory is the last resort when there's no home either.
+fn resolve(app_env: &str, xdg_env: &str, home_default: &[&str]) -> PathBuf {
+    if let Some(dir) = non_empty_var(app_env) {
+        return dir;
+    }
+
+    // The spec says relative paths are invalid and should be ignored
+    if let Some(dir) = non_empty_var(xdg_env).filter(|dir| dir.is_absolute()) {
+        return dir.join(APP_DIR);
+    }
+
+    match home_dir() {
+        Some(home) => home_default
+            .iter()
+            .fold(home, |dir, part| dir.join(part))
+            .join(APP_DIR),
+        None => PathBuf::from("."),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    non_empty_var("HOME").or_else(|| non_empty_var("USERPROFILE"))
+}
+
+fn non_empty_var(name: &str) -> Option<PathBuf> {
+    var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
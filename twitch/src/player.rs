@@ -0,0 +1,142 @@
+use crate::{channel::Channel, paths, quality::DEFAULT_QUALITY, storage};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const PLAYERS_FILE: &str = "players.toml";
+
+const HANDLE_PLACEHOLDER: &str = "{handle}";
+const URL_PLACEHOLDER: &str = "{url}";
+const QUALITY_PLACEHOLDER: &str = "{quality}";
+const TITLE_PLACEHOLDER: &str = "{title}";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Player {
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+impl Player {
+    #[must_use]
+    pub fn new(name: &str, command: &[&str]) -> Self {
+        Self {
+            name: String::from(name),
+            command: command.iter().map(|arg| String::from(*arg)).collect(),
+        }
+    }
+
+    /// Program and arguments for this player with every placeholder filled in for `channel`.
+    #[must_use]
+    pub fn command(&self, channel: &Channel) -> Option<(String, Vec<String>)> {
+        let mut argv = self.command.iter().map(|arg| expand(arg, channel));
+
+        let program = argv.next().filter(|program| !program.is_empty())?;
+
+        Some((program, argv.collect()))
+    }
+
+    #[must_use]
+    pub fn uses_quality(&self) -> bool {
+        self.command
+            .iter()
+            .any(|arg| arg.contains(QUALITY_PLACEHOLDER))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Players {
+    pub default: String,
+    pub profiles: Vec<Player>,
+}
+
+impl Default for Players {
+    fn default() -> Self {
+        Self {
+            default: String::from("streamlink"),
+            profiles: vec![
+                Player::new(
+                    "streamlink",
+                    &[
+                        "streamlink",
+                        "--title",
+                        TITLE_PLACEHOLDER,
+                        URL_PLACEHOLDER,
+                        QUALITY_PLACEHOLDER,
+                    ],
+                ),
+                Player::new(
+                    "vlc",
+                    &[
+                        "streamlink",
+                        "--player",
+                        "vlc",
+                        URL_PLACEHOLDER,
+                        QUALITY_PLACEHOLDER,
+                    ],
+                ),
+                Player::new("mpv", &["mpv", URL_PLACEHOLDER]),
+            ],
+        }
+    }
+}
+
+impl Players {
+    #[allow(clippy::missing_errors_doc)]
+    pub fn load_from_file(file: &Path) -> Result<Self> {
+        storage::load(file)
+    }
+
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from_file(&paths::config_file(PLAYERS_FILE)).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Player> {
+        self.profiles.iter().find(|player| player.name == name)
+    }
+
+    /// Every profile, with and without chat, starting with `preferred` (if it exists) and then the
+    /// configured default.
+    #[must_use]
+    pub fn choices(&self, preferred: Option<&str>) -> Vec<(Player, bool)> {
+        let mut ordered: Vec<&Player> = Vec::with_capacity(self.profiles.len());
+
+        for name in preferred.into_iter().chain([self.default.as_str()]) {
+            if let Some(player) = self.get(name) {
+                if !ordered.iter().any(|existing| existing.name == player.name) {
+                    ordered.push(player);
+                }
+            }
+        }
+
+        for player in &self.profiles {
+            if !ordered.iter().any(|existing| existing.name == player.name) {
+                ordered.push(player);
+            }
+        }
+
+        ordered
+            .into_iter()
+            .flat_map(|player| [(player.clone(), false), (player.clone(), true)])
+            .collect()
+    }
+}
+
+fn expand(arg: &str, channel: &Channel) -> String {
+    arg.replace(HANDLE_PLACEHOLDER, &channel.handle)
+        .replace(URL_PLACEHOLDER, &channel.url())
+        .replace(
+            QUALITY_PLACEHOLDER,
+            channel.quality.as_deref().unwrap_or(DEFAULT_QUALITY),
+        )
+        .replace(
+            TITLE_PLACEHOLDER,
+            channel
+                .stream
+                .as_ref()
+                .map_or(channel.friendly_name.as_str(), |stream| {
+                    stream.title.as_str()
+                }),
+        )
+}
@@ -0,0 +1,78 @@
+use crate::channel::Channel;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+use tokio::process::Command;
+
+pub const DEFAULT_QUALITY: &str = "best";
+
+const QUALITY_PROGRAM: &str = "streamlink";
+const BEST: &str = "best";
+const WORST: &str = "worst";
+const AUDIO_ONLY: &str = "audio_only";
+
+/// Qualities the channel is currently streaming in, best first, as reported by streamlink.
+#[allow(clippy::missing_errors_doc)]
+pub async fn available(channel: &Channel) -> Result<Vec<String>> {
+    let output = Command::new(QUALITY_PROGRAM)
+        .arg("--json")
+        .arg(channel.url())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .with_context(|| format!("Could not run {QUALITY_PROGRAM}"))?;
+
+    let json: Value = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Could not parse {QUALITY_PROGRAM} output"))?;
+
+    if let Some(error) = json.get("error").and_then(Value::as_str) {
+        anyhow::bail!("{error}");
+    }
+
+    let mut qualities: Vec<String> = json
+        .get("streams")
+        .and_then(Value::as_object)
+        .map(|streams| streams.keys().cloned().collect())
+        .unwrap_or_default();
+
+    qualities.sort_by(|a, b| compare(a, b));
+
+    Ok(qualities)
+}
+
+/// Moves `preferred` to the front if it's one of the available qualities.
+#[must_use]
+pub fn with_preferred(mut qualities: Vec<String>, preferred: Option<&str>) -> Vec<String> {
+    if let Some(index) = preferred.and_then(|preferred| {
+        qualities
+            .iter()
+            .position(|quality| quality.as_str() == preferred)
+    }) {
+        let quality = qualities.remove(index);
+        qualities.insert(0, quality);
+    }
+
+    qualities
+}
+
+// best > 1080p60 > 1080p > 720p60 > ... > audio_only > worst
+fn compare(a: &str, b: &str) -> Ordering {
+    rank(b).cmp(&rank(a)).then_with(|| a.cmp(b))
+}
+
+fn rank(quality: &str) -> (u8, u32, u32) {
+    match quality {
+        BEST => (3, 0, 0),
+        WORST => (0, 0, 0),
+        AUDIO_ONLY => (1, 0, 0),
+        _ => {
+            let (resolution, framerate) = quality.split_once('p').unwrap_or((quality, ""));
+
+            (
+                2,
+                resolution.parse().unwrap_or(0),
+                framerate.parse().unwrap_or(30),
+            )
+        }
+    }
+}
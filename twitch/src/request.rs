@@ -0,0 +1,204 @@
+use rand::Rng;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::{mpsc::UnboundedSender, Mutex},
+    time::sleep,
+};
+use tracing::{debug, warn};
+use twitch_api::{
+    client::{BoxedFuture, Bytes, Request, Response},
+    HttpClient,
+};
+
+const RATELIMIT_REMAINING: &str = "ratelimit-remaining";
+const RATELIMIT_RESET: &str = "ratelimit-reset";
+
+// Start queueing requests once there are this few points left before the bucket resets
+const LOW_BUDGET: u32 = 5;
+const MAX_RESET_WAIT: Duration = Duration::from_secs(60);
+
+const MAX_RETRIES: u32 = 3;
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct Budget {
+    remaining: Option<u32>,
+    reset: Option<u64>,
+}
+
+impl Budget {
+    fn wait(&self) -> Option<Duration> {
+        match self.remaining {
+            Some(remaining) if remaining <= LOW_BUDGET => self.reset.and_then(until),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, response: &Response) {
+        if let Some(remaining) = header(response, RATELIMIT_REMAINING) {
+            self.remaining = u32::try_from(remaining).ok();
+        }
+
+        if let Some(reset) = header(response, RATELIMIT_RESET) {
+            self.reset = Some(reset);
+        }
+    }
+}
+
+/// The Helix rate limit points left, shared by every client made with it so requests made from
+/// anywhere count against the same budget.
+#[derive(Clone, Default)]
+pub struct RateLimit(Arc<Mutex<Budget>>);
+
+/// Helix client that keeps track of the rate limit headers, queues requests while the budget is
+/// low and retries transient failures with jittered exponential backoff.
+#[derive(Clone)]
+pub struct RateLimitedClient {
+    client: reqwest::Client,
+    budget: Arc<Mutex<Budget>>,
+    throttled: Option<UnboundedSender<Duration>>,
+}
+
+impl RateLimitedClient {
+    /// `throttled` is told how long requests are being held back for whenever that happens.
+    #[must_use]
+    pub fn new(
+        client: reqwest::Client,
+        rate_limit: &RateLimit,
+        throttled: Option<UnboundedSender<Duration>>,
+    ) -> Self {
+        Self {
+            client,
+            budget: rate_limit.0.clone(),
+            throttled,
+        }
+    }
+
+    async fn send(&self, request: Request) -> Result<Response, reqwest::Error> {
+        let (parts, body) = request.into_parts();
+
+        let mut attempt: u32 = 0;
+
+        loop {
+            self.wait_for_budget().await;
+
+            let request = rebuild(&parts, body.clone());
+
+            match self.client.req(request).await {
+                Ok(response) => {
+                    self.budget.lock().await.update(&response);
+
+                    let status = response.status();
+
+                    if attempt >= MAX_RETRIES
+                        || !(status.as_u16() == 429 || status.is_server_error())
+                    {
+                        return Ok(response);
+                    }
+
+                    let wait = if status.as_u16() == 429 {
+                        let wait = header(&response, RATELIMIT_RESET)
+                            .and_then(until)
+                            .unwrap_or_else(|| backoff(attempt));
+
+                        self.report(wait);
+
+                        warn!("Rate limited by Twitch, waiting {}s", wait.as_secs());
+
+                        wait + jitter(BACKOFF_BASE)
+                    } else {
+                        debug!("Twitch answered {status}, retrying");
+
+                        backoff(attempt)
+                    };
+
+                    sleep(wait).await;
+                }
+                Err(e) => {
+                    if attempt >= MAX_RETRIES || !(e.is_timeout() || e.is_connect()) {
+                        return Err(e);
+                    }
+
+                    debug!("Request to Twitch failed, retrying: {e}");
+
+                    sleep(backoff(attempt)).await;
+                }
+            }
+
+            attempt += 1;
+        }
+    }
+
+    async fn wait_for_budget(&self) {
+        // Holding the lock while waiting makes every other request queue up behind this one
+        let mut budget = self.budget.lock().await;
+
+        if let Some(wait) = budget.wait() {
+            self.report(wait);
+
+            sleep(wait).await;
+
+            budget.remaining = None;
+        }
+
+        if let Some(remaining) = budget.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    fn report(&self, wait: Duration) {
+        if let Some(throttled) = &self.throttled {
+            let _result = throttled.send(wait);
+        }
+    }
+}
+
+impl HttpClient for RateLimitedClient {
+    type Error = reqwest::Error;
+
+    fn req(&self, request: Request) -> BoxedFuture<'_, Result<Response, Self::Error>> {
+        Box::pin(self.send(request))
+    }
+}
+
+fn rebuild(parts: &http::request::Parts, body: Bytes) -> Request {
+    let mut request = Request::new(body);
+
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+
+    request
+}
+
+fn header(response: &Response, name: &str) -> Option<u64> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+// Time left until the given unix timestamp, capped so a bad header can't stall everything
+fn until(timestamp: u64) -> Option<Duration> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+
+    Duration::from_secs(timestamp)
+        .checked_sub(now)
+        .map(|wait| wait.min(MAX_RESET_WAIT))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let delay = BACKOFF_BASE * 2_u32.saturating_pow(attempt);
+
+    delay + jitter(delay)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn jitter(max: Duration) -> Duration {
+    Duration::from_millis(rand::thread_rng().gen_range(0..=max.as_millis() as u64))
+}
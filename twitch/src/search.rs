@@ -0,0 +1,108 @@
+use crate::{
+    account::Account,
+    channel::{
+        status::Status,
+        stream::{parse_timestamp, StreamInfo},
+        Channel,
+    },
+    request::RateLimit,
+    status_service::{SharedService, StatusService},
+};
+use anyhow::{Context, Result};
+use tokio::{spawn, sync::mpsc::UnboundedSender};
+use twitch_api::helix::search::SearchChannelsRequest;
+
+const RESULT_COUNT: usize = 20;
+
+/// The query searched for and what came back, so late answers to older queries can be dropped.
+pub type SearchUpdate = (String, std::result::Result<Vec<Channel>, String>);
+
+/// Searches channels in the background, validating the access token on the first query and
+/// reusing it for the next ones. Clones share the same validated token.
+#[derive(Clone, Default)]
+pub struct Searcher {
+    service: SharedService,
+}
+
+impl Searcher {
+    pub fn search(
+        &self,
+        account: &Account,
+        rate_limit: &RateLimit,
+        query: &str,
+        sender: &UnboundedSender<SearchUpdate>,
+    ) {
+        let searcher = self.clone();
+        let account = account.clone();
+        let rate_limit = rate_limit.clone();
+        let query = query.to_owned();
+        let tx = (*sender).clone();
+
+        spawn(async move {
+            let results = searcher
+                .channels(&account, &rate_limit, &query)
+                .await
+                .map_err(|e| format!("{e:#}"));
+
+            let _result = tx.send((query, results));
+        });
+    }
+
+    async fn channels(
+        &self,
+        account: &Account,
+        rate_limit: &RateLimit,
+        query: &str,
+    ) -> Result<Vec<Channel>> {
+        let service = self.service.get(account, rate_limit).await?;
+
+        let results = search(&service, query).await;
+
+        // The token may be why it failed, so it is validated again next time
+        if results.is_err() {
+            self.service.forget().await;
+        }
+
+        results
+    }
+}
+
+/// Channels matching `query`, as Helix orders them. Only live channels come with a stream.
+async fn search(service: &StatusService, query: &str) -> Result<Vec<Channel>> {
+    let req = SearchChannelsRequest::query(query).first(RESULT_COUNT);
+
+    let response = service
+        .helix()
+        .req_get(req, service.token())
+        .await
+        .with_context(|| "Could not search channels")?;
+
+    Ok(response
+        .data
+        .into_iter()
+        .map(|result| {
+            let mut channel = Channel::new(
+                result.display_name.to_string(),
+                result.broadcaster_login.to_string(),
+            );
+
+            if result.is_live {
+                channel.status = Status::Online;
+                channel.stream = Some(StreamInfo {
+                    game: result.game_name,
+                    title: result.title,
+                    started_at: result
+                        .started_at
+                        .and_then(|started_at| parse_timestamp(started_at.as_str())),
+                    language: result.broadcaster_language,
+                    thumbnail_url: result.thumbnail_url,
+                    ..StreamInfo::default()
+                });
+            } else {
+                channel.status = Status::Offline;
+            }
+
+            channel
+        })
+        .collect())
+}
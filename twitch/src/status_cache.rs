@@ -0,0 +1,296 @@
+use crate::{
+    channel::{stream::StreamInfo, Channel},
+    eventsub::StreamEvent,
+    paths,
+    request::RateLimit,
+    status::Status,
+    status_service::StatusUpdate,
+    storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    io::Result,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+const STATUS_CACHE_FILE: &str = "statuses.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Entry {
+    status: Status,
+    stream: Option<StreamInfo>,
+    checked: SystemTime,
+}
+
+impl Entry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        // A clock that went backwards makes the entry stale rather than fresh forever
+        self.checked
+            .elapsed()
+            .map_or(false, |elapsed| elapsed < ttl)
+    }
+}
+
+/// Last known status of every channel checked so far, keyed by handle. Clones share the same
+/// entries so every view sees what any other view fetched, and the same Helix rate limit so their
+/// requests don't each think they have the whole budget.
+#[derive(Clone)]
+pub struct StatusCache {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+    rate_limit: RateLimit,
+    ttl: Duration,
+    persistent: bool,
+}
+
+impl StatusCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            rate_limit: RateLimit::default(),
+            ttl,
+            persistent: false,
+        }
+    }
+
+    /// Starts from the statuses saved by the last run and saves every update.
+    #[must_use]
+    pub fn persistent(ttl: Duration) -> Self {
+        let entries = load_from_file(&paths::cache_file(STATUS_CACHE_FILE)).unwrap_or_default();
+
+        Self {
+            entries: Arc::new(Mutex::new(entries)),
+            rate_limit: RateLimit::default(),
+            ttl,
+            persistent: true,
+        }
+    }
+
+    #[must_use]
+    pub const fn rate_limit(&self) -> &RateLimit {
+        &self.rate_limit
+    }
+
+    /// Fills in the last known status of each channel, even a stale one, and returns the channels
+    /// that should be checked again.
+    #[must_use]
+    pub fn apply(&self, channels: &mut [Channel]) -> Vec<Channel> {
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return channels.to_vec(),
+        };
+
+        let mut stale: Vec<Channel> = Vec::new();
+
+        for channel in channels.iter_mut() {
+            match entries.get(&key(&channel.handle)) {
+                Some(entry) => {
+                    channel.status = entry.status.clone();
+                    channel.stream = entry.stream.clone();
+
+                    if !entry.is_fresh(self.ttl) {
+                        stale.push(channel.clone());
+                    }
+                }
+                None => stale.push(channel.clone()),
+            }
+        }
+
+        stale
+    }
+
+    /// Splits `handles` into updates that can be answered from the cache and handles that need a
+    /// request.
+    #[must_use]
+    pub fn split(&self, handles: &[String]) -> (Vec<StatusUpdate>, Vec<String>) {
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return (Vec::new(), handles.to_vec()),
+        };
+
+        let mut cached: Vec<StatusUpdate> = Vec::new();
+        let mut stale: Vec<String> = Vec::new();
+
+        for handle in handles {
+            match entries.get(&key(handle)) {
+                Some(entry) if entry.is_fresh(self.ttl) => {
+                    cached.push((handle.clone(), (entry.status.clone(), entry.stream.clone())));
+                }
+                _ => stale.push(handle.clone()),
+            }
+        }
+
+        (cached, stale)
+    }
+
+    /// Applies what EventSub said about a channel on top of what was known of it, and stores and
+    /// returns the result. Going live is left to a check, see [`Self::expire`], as EventSub doesn't
+    /// send the title, game or viewers, and an update to a channel not known to be live changes
+    /// nothing.
+    #[must_use]
+    pub fn change(&self, event: &StreamEvent) -> Option<StatusUpdate> {
+        let update = match event {
+            StreamEvent::Online { .. } => return None,
+            StreamEvent::Offline { .. } => (Status::Offline, None),
+            StreamEvent::Updated { title, game, .. } => {
+                let known = self
+                    .entries
+                    .lock()
+                    .ok()?
+                    .get(&key(event.handle()))
+                    .filter(|entry| entry.status == Status::Online)
+                    .and_then(|entry| entry.stream.clone())?;
+
+                let stream = StreamInfo {
+                    title: title.clone(),
+                    game: game.clone(),
+                    ..known
+                };
+
+                (Status::Online, Some(stream))
+            }
+        };
+
+        let update = (event.handle().to_owned(), update);
+
+        self.store(std::slice::from_ref(&update));
+
+        Some(update)
+    }
+
+    /// Makes the next check of `handle` ask Twitch again, keeping what is known until then.
+    pub fn expire(&self, handle: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(entry) = entries.get_mut(&key(handle)) {
+                entry.checked = UNIX_EPOCH;
+            }
+        }
+    }
+
+    /// Stores the statuses that came with `channels`, like followed or browsed channels do.
+    pub fn store_channels(&self, channels: &[Channel]) {
+        let updates: Vec<StatusUpdate> = channels
+            .iter()
+            .map(|channel| {
+                (
+                    channel.handle.clone(),
+                    (channel.status.clone(), channel.stream.clone()),
+                )
+            })
+            .collect();
+
+        self.store(&updates);
+    }
+
+    pub fn store(&self, updates: &[StatusUpdate]) {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        let checked = SystemTime::now();
+
+        for (handle, (status, stream)) in updates {
+            // Failed checks say nothing about the channel, keep what was known before
+            if matches!(status, Status::Online | Status::Offline) {
+                entries.insert(
+                    key(handle),
+                    Entry {
+                        status: status.clone(),
+                        stream: stream.clone(),
+                        checked,
+                    },
+                );
+            }
+        }
+
+        if self.persistent {
+            if let Err(e) = save_to_file(&paths::cache_file(STATUS_CACHE_FILE), &entries) {
+                warn!("Could not save channel statuses: {e}");
+            }
+        }
+    }
+}
+
+// Handles are case-insensitive on Twitch
+fn key(handle: &str) -> String {
+    handle.to_ascii_lowercase()
+}
+
+fn load_from_file(file: &Path) -> Result<HashMap<String, Entry>> {
+    let data: String = read_to_string(file)?;
+
+    Ok(serde_json::from_str(data.as_str())?)
+}
+
+fn save_to_file(file: &Path, entries: &HashMap<String, Entry>) -> Result<()> {
+    storage::write_atomic(file, &serde_json::to_string(entries)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(title: &str) -> StatusUpdate {
+        let stream = StreamInfo {
+            title: title.to_owned(),
+            viewers: Some(10),
+            ..StreamInfo::default()
+        };
+
+        (String::from("Channel"), (Status::Online, Some(stream)))
+    }
+
+    fn updated(title: &str) -> StreamEvent {
+        StreamEvent::Updated {
+            handle: String::from("channel"),
+            title: title.to_owned(),
+            game: String::from("Game"),
+        }
+    }
+
+    #[test]
+    fn update_keeps_what_else_was_known() {
+        let cache = StatusCache::new(Duration::from_secs(60));
+        cache.store(&[live("Before")]);
+
+        let (_, (status, stream)) = cache.change(&updated("After")).unwrap();
+        let stream = stream.unwrap();
+
+        assert!(status == Status::Online);
+        assert_eq!(stream.title, "After");
+        assert_eq!(stream.game, "Game");
+        assert_eq!(stream.viewers, Some(10));
+    }
+
+    #[test]
+    fn update_to_channel_not_live_is_ignored() {
+        let cache = StatusCache::new(Duration::from_secs(60));
+        cache.store(&[(String::from("channel"), (Status::Offline, None))]);
+
+        assert!(cache.change(&updated("After")).is_none());
+    }
+
+    #[test]
+    fn online_leaves_the_channel_to_a_check() {
+        let cache = StatusCache::new(Duration::from_secs(60));
+        cache.store(&[(String::from("channel"), (Status::Offline, None))]);
+
+        let online = StreamEvent::Online {
+            handle: String::from("channel"),
+            started_at: None,
+        };
+        assert!(cache.change(&online).is_none());
+
+        cache.expire("CHANNEL");
+        let (cached, missing) = cache.split(&[String::from("channel")]);
+
+        assert!(cached.is_empty());
+        assert_eq!(missing, vec![String::from("channel")]);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    account::Account,
+    channel::{
+        status::Status,
+        stream::{parse_timestamp, StreamInfo},
+    },
+    request::{RateLimit, RateLimitedClient},
+    secret::{Expose, Secret},
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::{mpsc::UnboundedSender, Mutex};
+use tracing::warn;
+use twitch_api::{
+    helix::{ClientRequestError, HelixRequestGetError, Request, RequestGet},
+    twitch_oauth2::{AccessToken, Scope, UserToken},
+    HelixClient,
+};
+
+// Helix accepts up to 100 logins per Get Streams request
+const BATCH_SIZE: usize = 100;
+
+pub type StatusUpdate = (String, (Status, Option<StreamInfo>));
+
+// Get Streams, asked for directly because twitch_api's `Stream` doesn't have the tag names
+#[derive(Serialize)]
+struct GetStreams<'a> {
+    user_login: &'a [String],
+    first: usize,
+}
+
+impl Request for GetStreams<'_> {
+    const PATH: &'static str = "streams";
+    const SCOPE: &'static [Scope] = &[];
+
+    type Response = Vec<Stream>;
+}
+
+impl RequestGet for GetStreams<'_> {}
+
+#[derive(Deserialize, PartialEq, Eq)]
+pub(crate) struct Stream {
+    pub(crate) user_login: String,
+    pub(crate) user_name: String,
+    game_name: String,
+    title: String,
+    pub(crate) viewer_count: usize,
+    started_at: String,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    language: String,
+    is_mature: bool,
+    thumbnail_url: String,
+}
+
+impl From<&Stream> for StreamInfo {
+    fn from(stream: &Stream) -> Self {
+        Self {
+            game: stream.game_name.clone(),
+            title: stream.title.clone(),
+            viewers: Some(stream.viewer_count),
+            started_at: parse_timestamp(&stream.started_at),
+            tags: stream.tags.clone().unwrap_or_default(),
+            language: stream.language.clone(),
+            mature: stream.is_mature,
+            thumbnail_url: stream.thumbnail_url.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct StatusService {
+    client: RateLimitedClient,
+    token: UserToken,
+}
+
+/// A service kept between requests, so the access token is only validated for the first one.
+/// Clones share the same service.
+#[derive(Clone, Default)]
+pub struct SharedService(Arc<Mutex<Option<StatusService>>>);
+
+impl SharedService {
+    /// The kept service, validating the access token first if there isn't one yet.
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn get(&self, account: &Account, rate_limit: &RateLimit) -> Result<StatusService> {
+        // Held while validating so a second request waits for the token instead of validating it
+        let mut kept = self.0.lock().await;
+
+        if let Some(service) = kept.as_ref() {
+            return Ok(service.clone());
+        }
+
+        let service = StatusService::new(&account.access_token(), rate_limit, None).await?;
+        *kept = Some(service.clone());
+
+        Ok(service)
+    }
+
+    /// Drops the kept service after a failed request, as the token may be why, so it is
+    /// validated again next time.
+    pub async fn forget(&self) {
+        *self.0.lock().await = None;
+    }
+}
+
+impl StatusService {
+    /// Validates the access token once so it can be reused for every batch. `rate_limit` and
+    /// `throttled` are handed to the request layer.
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn new(
+        user_access_token: &Secret,
+        rate_limit: &RateLimit,
+        throttled: Option<UnboundedSender<Duration>>,
+    ) -> Result<Self> {
+        let http_client = reqwest::Client::default();
+
+        let token = UserToken::from_existing(
+            &http_client,
+            AccessToken::new(user_access_token.expose_value().to_owned()),
+            None,
+            None,
+        )
+        .await
+        .with_context(|| "Could not validate access token")?;
+
+        Ok(Self {
+            client: RateLimitedClient::new(http_client, rate_limit, throttled),
+            token,
+        })
+    }
+
+    pub(crate) fn helix(&self) -> HelixClient<'_, RateLimitedClient> {
+        HelixClient::with_client(self.client.clone())
+    }
+
+    pub(crate) const fn client(&self) -> &RateLimitedClient {
+        &self.client
+    }
+
+    pub(crate) const fn token(&self) -> &UserToken {
+        &self.token
+    }
+
+    /// Status and game of every handle, one Get Streams request per 100 handles.
+    pub async fn check(&self, handles: &[String]) -> Vec<StatusUpdate> {
+        let client = self.helix();
+
+        let mut updates: Vec<StatusUpdate> = Vec::with_capacity(handles.len());
+
+        for batch in handles.chunks(BATCH_SIZE) {
+            let req = GetStreams {
+                user_login: batch,
+                first: BATCH_SIZE,
+            };
+
+            match client.req_get(req, &self.token).await {
+                Ok(response) => {
+                    updates.extend(batch.iter().map(|handle| {
+                        let stream = response
+                            .data
+                            .iter()
+                            .find(|stream| stream.user_login.eq_ignore_ascii_case(handle));
+
+                        let status = stream.map_or((Status::Offline, None), |stream_data| {
+                            (Status::Online, Some(StreamInfo::from(stream_data)))
+                        });
+
+                        (handle.clone(), status)
+                    }));
+                }
+                Err(e) => {
+                    warn!("Could not get channel status: {e}");
+
+                    if is_rate_limited(&e) {
+                        updates.extend(throttled(batch));
+                    } else {
+                        updates.extend(unknown(batch));
+                    }
+                }
+            }
+        }
+
+        updates
+    }
+}
+
+#[must_use]
+pub fn unknown(handles: &[String]) -> Vec<StatusUpdate> {
+    handles
+        .iter()
+        .map(|handle| (handle.clone(), (Status::Unknown, None)))
+        .collect()
+}
+
+#[must_use]
+pub fn throttled(handles: &[String]) -> Vec<StatusUpdate> {
+    handles
+        .iter()
+        .map(|handle| (handle.clone(), (Status::Throttled, None)))
+        .collect()
+}
+
+// Retries already gave up by the time the error reaches here
+fn is_rate_limited(error: &ClientRequestError<reqwest::Error>) -> bool {
+    matches!(
+        error,
+        ClientRequestError::HelixRequestGetError(HelixRequestGetError::Error { status, .. })
+            if status.as_u16() == 429
+    )
+}
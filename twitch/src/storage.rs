@@ -0,0 +1,216 @@
+use crate::paths;
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs::{copy, create_dir_all, read_dir, read_to_string, remove_file, rename, write},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+// What hand edited files used to be written as, and what they're renamed to once migrated
+const LEGACY_EXTENSION: &str = "json";
+const MIGRATED_EXTENSION: &str = "json.bak";
+const EXTENSION: &str = "toml";
+
+// What older versions read from the current directory, by the name they have now
+const LEGACY_CONFIG_FILES: [&str; 4] = [
+    "account.toml",
+    "players.toml",
+    "settings.toml",
+    "theme.toml",
+];
+const LEGACY_DATA_FILES: [&str; 1] = ["favourites.toml"];
+const LEGACY_LISTS_DIR: &str = "lists";
+
+/// Reads a TOML file, first migrating the `.json` file next to it if only that exists.
+#[allow(clippy::missing_errors_doc)]
+pub fn load<T: DeserializeOwned + Serialize>(file: &Path) -> Result<T> {
+    if !file.exists() {
+        migrate::<T>(file)?;
+    }
+
+    let data: String =
+        read_to_string(file).with_context(|| format!("Could not read {}", file.display()))?;
+
+    toml::from_str(data.as_str()).with_context(|| format!("Could not parse {}", file.display()))
+}
+
+#[allow(clippy::missing_errors_doc)]
+pub fn save<T: Serialize>(file: &Path, value: &T) -> Result<()> {
+    // Going through a Value puts plain values before tables, which TOML needs
+    let contents = toml::Value::try_from(value)
+        .and_then(|value| toml::to_string_pretty(&value))
+        .with_context(|| format!("Could not serialise {}", file.display()))?;
+
+    write_atomic(file, &contents).with_context(|| format!("Could not write {}", file.display()))
+}
+
+/// Whether `file` or a JSON copy of it waiting to be migrated exists.
+#[must_use]
+pub fn exists(file: &Path) -> bool {
+    file.exists() || file.with_extension(LEGACY_EXTENSION).exists()
+}
+
+/// Deletes `file` along with the JSON file it was or would be migrated from, so that can't bring
+/// it back. Files that aren't there are fine, otherwise the first failure is returned once every
+/// file was tried.
+#[allow(clippy::missing_errors_doc)]
+pub fn remove(file: &Path) -> std::io::Result<()> {
+    [
+        file.to_path_buf(),
+        file.with_extension(LEGACY_EXTENSION),
+        file.with_extension(MIGRATED_EXTENSION),
+    ]
+    .iter()
+    .map(|file| match remove_file(file) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    })
+    .fold(Ok(()), std::io::Result::and)
+}
+
+// Written next to the file and renamed over it, so a crash can't leave it half written
+pub(crate) fn write_atomic(file: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = file.parent() {
+        create_dir_all(dir)?;
+    }
+
+    // Keeps the extension, so files that only differ in it don't share the temporary file
+    let temporary = file.with_extension(file.extension().map_or_else(
+        || String::from("tmp"),
+        |extension| format!("{}.tmp", extension.to_string_lossy()),
+    ));
+
+    write(&temporary, contents)?;
+    rename(temporary, file)
+}
+
+/// Copies what older versions kept in the current directory to the config and data directories,
+/// JSON files included as they are converted once read. Nothing already in those directories is
+/// touched, so only the first start after updating copies anything.
+pub fn migrate_current_dir() {
+    let files = LEGACY_CONFIG_FILES
+        .iter()
+        .map(|name| (*name, paths::config_file(name)))
+        .chain(
+            LEGACY_DATA_FILES
+                .iter()
+                .map(|name| (*name, paths::data_file(name))),
+        );
+
+    for (name, target) in files {
+        copy_legacy(Path::new(name), &target);
+    }
+
+    let lists = paths::data_file(LEGACY_LISTS_DIR);
+
+    if lists.exists() {
+        return;
+    }
+
+    if let Ok(entries) = read_dir(LEGACY_LISTS_DIR) {
+        for entry in entries.flatten() {
+            let file = entry.path().with_extension(EXTENSION);
+
+            if let Some(name) = file.file_name() {
+                copy_legacy(&file, &lists.join(name));
+            }
+        }
+    }
+}
+
+// Copies `file`, or the JSON file next to it, unless `target` or its JSON file exists
+fn copy_legacy(file: &Path, target: &Path) {
+    if exists(target) {
+        return;
+    }
+
+    let copies: [(PathBuf, PathBuf); 2] = [
+        (file.to_path_buf(), target.to_path_buf()),
+        (
+            file.with_extension(LEGACY_EXTENSION),
+            target.with_extension(LEGACY_EXTENSION),
+        ),
+    ];
+
+    for (from, to) in copies.iter().filter(|(from, _)| from.is_file()) {
+        let copied = to
+            .parent()
+            .map_or(Ok(()), create_dir_all)
+            .and_then(|()| copy(from, to));
+
+        match copied {
+            Ok(_) => info!("Copied {} to {}", from.display(), to.display()),
+            Err(e) => warn!("Could not copy {} to {}: {e}", from.display(), to.display()),
+        }
+    }
+}
+
+// The JSON file is kept as a backup rather than deleted, and is ignored from then on
+fn migrate<T: DeserializeOwned + Serialize>(file: &Path) -> Result<()> {
+    let legacy = file.with_extension(LEGACY_EXTENSION);
+
+    if !legacy.exists() {
+        return Ok(());
+    }
+
+    let data: String =
+        read_to_string(&legacy).with_context(|| format!("Could not read {}", legacy.display()))?;
+
+    let value: T = serde_json::from_str(data.as_str())
+        .with_context(|| format!("Could not parse {}", legacy.display()))?;
+
+    save(file, &value)?;
+
+    info!("Converted {} to {}", legacy.display(), file.display());
+
+    rename(&legacy, file.with_extension(MIGRATED_EXTENSION))
+        .with_context(|| format!("Could not move {} out of the way", legacy.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, fs::remove_dir_all, process};
+
+    // A directory of its own for each test, as they run at the same time
+    fn scratch(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("twitch_launcher_{name}_{}", process::id()));
+        let _result = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn remove_takes_the_legacy_files_too() {
+        let dir = scratch("remove_legacy");
+        let file = dir.join("list.toml");
+
+        for name in ["list.toml", "list.json", "list.json.bak"] {
+            write(dir.join(name), "").unwrap();
+        }
+
+        remove(&file).unwrap();
+
+        assert!(!exists(&file));
+        assert!(!file.with_extension(MIGRATED_EXTENSION).exists());
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn remove_is_fine_with_only_the_legacy_file() {
+        let dir = scratch("remove_only_legacy");
+        let file = dir.join("list.toml");
+
+        write(dir.join("list.json"), "").unwrap();
+
+        remove(&file).unwrap();
+
+        assert!(!exists(&file));
+
+        remove_dir_all(dir).unwrap();
+    }
+}
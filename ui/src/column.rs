@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use twitch::channel::Channel;
+
+const THUMBNAIL_WIDTH: u32 = 1280;
+const THUMBNAIL_HEIGHT: u32 = 720;
+
+/// Something shown next to the channel name in a channel list.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Status,
+    Game,
+    Title,
+    Viewers,
+    Uptime,
+    Language,
+    Tags,
+    Mature,
+    Thumbnail,
+}
+
+impl Column {
+    #[must_use]
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::Status, Self::Viewers, Self::Uptime, Self::Game]
+    }
+
+    // Longest a column is allowed to get before it is cut short
+    #[must_use]
+    pub const fn max_width(self) -> usize {
+        match self {
+            Self::Title => 40,
+            Self::Game | Self::Tags => 30,
+            Self::Thumbnail => 100,
+            Self::Status | Self::Viewers | Self::Uptime | Self::Language | Self::Mature => 10,
+        }
+    }
+
+    #[must_use]
+    pub fn text(self, channel: &Channel) -> String {
+        match (self, &channel.stream) {
+            (Self::Status, _) => channel.status.message().to_owned(),
+            // Everything else is about the stream
+            (_, None) => String::new(),
+            (Self::Game, Some(stream)) => stream.game.clone(),
+            (Self::Title, Some(stream)) => stream.title.clone(),
+            (Self::Viewers, Some(stream)) => stream.viewers.map(viewers).unwrap_or_default(),
+            (Self::Uptime, Some(stream)) => stream.uptime().map(uptime).unwrap_or_default(),
+            (Self::Language, Some(stream)) => stream.language.clone(),
+            (Self::Tags, Some(stream)) => stream.tags.join(", "),
+            (Self::Mature, Some(stream)) => String::from(if stream.mature { "18+" } else { "" }),
+            (Self::Thumbnail, Some(stream)) => stream.thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT),
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn viewers(count: usize) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+fn uptime(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+
+    match minutes / 60 {
+        0 => format!("{minutes}m"),
+        hours => format!("{hours}h {:02}m", minutes % 60),
+    }
+}
@@ -0,0 +1,113 @@
+use tui::layout::Rect;
+
+use crate::render::TAB_TITLES;
+
+/// Something on screen that reacts to the mouse, with the panel it is in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Target<P> {
+    Tab(usize),
+    Panel(P),
+    Row(P, usize),
+    Choice(usize),
+}
+
+/// Where the parts of a view that react to the mouse were last drawn, filled in while rendering.
+#[derive(Clone)]
+pub struct Hits<P> {
+    areas: Vec<(Rect, Target<P>)>,
+}
+
+impl<P: Copy> Default for Hits<P> {
+    fn default() -> Self {
+        Self { areas: Vec::new() }
+    }
+}
+
+impl<P: Copy> Hits<P> {
+    pub fn add(&mut self, area: Rect, target: Target<P>) {
+        self.areas.push((area, target));
+    }
+
+    /// The tab titles, laid out the way the tabs widget draws them.
+    pub fn tabs(&mut self, area: Rect) {
+        let mut x = area.x;
+
+        for (tab, title) in TAB_TITLES.iter().enumerate() {
+            // Titles are padded by a space on either side
+            let width = u16::try_from(title.chars().count())
+                .unwrap_or(u16::MAX)
+                .saturating_add(2);
+
+            let width = width.min(area.right().saturating_sub(x));
+
+            if width == 0 {
+                break;
+            }
+
+            self.add(
+                Rect {
+                    x,
+                    y: area.y,
+                    width,
+                    height: area.height,
+                },
+                Target::Tab(tab),
+            );
+
+            x = x.saturating_add(width);
+        }
+    }
+
+    /// A list drawn in `area` with `highlight` selected, as a row target over each visible item.
+    /// Items are one line high and the list is scrolled just enough to show the highlight, which
+    /// is how a fresh `ListState` is drawn.
+    pub fn list(&mut self, panel: P, area: Rect, len: usize, highlight: Option<usize>) {
+        self.add(area, Target::Panel(panel));
+
+        self.rows(area, len, highlight.unwrap_or(0), |index| {
+            Target::Row(panel, index)
+        });
+    }
+
+    /// Options of a choice drawn as a list in `area`.
+    pub fn choices(&mut self, area: Rect, len: usize, selected: usize) {
+        self.rows(area, len, selected, Target::Choice);
+    }
+
+    /// What is under the given cell, the last drawn first.
+    #[must_use]
+    pub fn at(&self, column: u16, row: u16) -> Option<Target<P>> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+            })
+            .map(|(_, target)| *target)
+    }
+
+    fn rows(
+        &mut self,
+        area: Rect,
+        len: usize,
+        highlight: usize,
+        target: impl Fn(usize) -> Target<P>,
+    ) {
+        let height = usize::from(area.height);
+        let offset = (highlight + 1).saturating_sub(height);
+
+        for (row, index) in (offset..len).take(height).enumerate() {
+            self.add(
+                Rect {
+                    x: area.x,
+                    y: area
+                        .y
+                        .saturating_add(u16::try_from(row).unwrap_or(u16::MAX)),
+                    width: area.width,
+                    height: 1,
+                },
+                target(index),
+            );
+        }
+    }
+}
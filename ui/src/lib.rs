@@ -1,3 +1,6 @@
+pub mod column;
+pub mod hit;
 pub mod panel;
 pub mod render;
 pub mod theme;
+pub mod toast;
@@ -58,3 +58,30 @@ impl Default for Lists {
         Self::Lists
     }
 }
+
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum Browse {
+    Categories,
+    Streams,
+}
+
+impl Panel for Browse {
+    fn left(&self) -> Self {
+        match *self {
+            Self::Categories | Self::Streams => Self::Categories,
+        }
+    }
+
+    fn right(&self) -> Self {
+        match *self {
+            Self::Categories | Self::Streams => Self::Streams,
+        }
+    }
+}
+
+impl Default for Browse {
+    #[inline]
+    fn default() -> Self {
+        Self::Categories
+    }
+}
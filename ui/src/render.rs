@@ -1,10 +1,17 @@
+mod browse;
 mod common;
+mod follows;
 mod home;
 mod lists;
 mod popup;
 pub mod startup;
+mod toast;
 
+pub use browse::{browse, BrowseList};
+pub(crate) use common::TAB_TITLES;
+pub use follows::follows;
 pub use home::home;
 pub use lists::lists;
 pub use popup::{choice, input, timed_info};
 pub use startup::{account_missing, starting};
+pub use toast::toasts;
@@ -0,0 +1,231 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use twitch::{browse::Category, channel::Channel};
+
+use crate::{
+    column::Column,
+    hit::{Hits, Target},
+    panel::Browse as BrowsePanel,
+    theme::{Elevation, Theme},
+};
+
+use super::common::{
+    animate_ellipsis, generate_app_layout, generate_background_widget, generate_channel_items,
+    generate_keys_widget, generate_tabs_widget, generate_title_with_info, HORIZONTAL_MARGIN,
+    VERTICAL_MARGIN,
+};
+
+/// What one side of the Browse tab is showing.
+pub struct BrowseList<'a, T> {
+    pub items: &'a [T],
+    pub highlight: usize,
+    pub loading: bool,
+    pub error: Option<&'a str>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn browse<B: Backend>(
+    theme: &Theme,
+    frame: &mut Frame<'_, B>,
+    categories: &BrowseList<'_, Category>,
+    open_category: Option<&Category>,
+    streams: &BrowseList<'_, Channel>,
+    columns: &[Column],
+    focused_panel: BrowsePanel,
+    keybinds: &[String],
+    timer: u64,
+) -> Hits<BrowsePanel> {
+    let mut hits = Hits::default();
+
+    let area = frame.size();
+
+    frame.render_widget(
+        generate_background_widget(theme.background.as_tui_colour()),
+        area,
+    );
+
+    let app_layout = generate_app_layout(area);
+
+    frame.render_widget(
+        generate_background_widget(theme.elevation(Elevation::Level1).as_tui_colour()),
+        app_layout[2],
+    );
+
+    let content_area = generate_content_area_layout(app_layout[2]);
+
+    frame.render_widget(generate_tabs_widget(3, theme), app_layout[0]);
+    hits.tabs(app_layout[0]);
+
+    let category_chunks = generate_browse_layout(content_area[0]);
+    let categories_focused = focused_panel == BrowsePanel::Categories;
+
+    hits.add(category_chunks[0], Target::Panel(BrowsePanel::Categories));
+
+    frame.render_widget(
+        generate_title_with_info(
+            "Top Categories",
+            &loading_info(categories, timer),
+            theme.elevation(Elevation::Level2).as_tui_colour(),
+            theme.primary.as_tui_colour(),
+            theme.text_dimmed.as_tui_colour(),
+            categories_focused,
+        ),
+        category_chunks[0],
+    );
+
+    if categories.items.is_empty() {
+        frame.render_widget(
+            generate_message_widget(theme, message(categories, "No categories to show", timer)),
+            category_chunks[1],
+        );
+    } else {
+        let mut list_state: ListState = ListState::default();
+        list_state.select(Some(categories.highlight));
+
+        let items = categories
+            .items
+            .iter()
+            .map(|category| {
+                ListItem::new(Spans::from(vec![Span::styled(
+                    format!(" {}", category.name),
+                    Style::default().fg(theme.text.as_tui_colour()),
+                )]))
+            })
+            .collect();
+
+        frame.render_stateful_widget(
+            generate_list_widget(theme, items, categories_focused),
+            category_chunks[1],
+            &mut list_state,
+        );
+
+        hits.list(
+            BrowsePanel::Categories,
+            category_chunks[1],
+            categories.items.len(),
+            Some(categories.highlight),
+        );
+    }
+
+    if let Some(category) = open_category {
+        let stream_chunks_with_margin = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(HORIZONTAL_MARGIN), Constraint::Min(1)].as_ref())
+            .split(content_area[1]);
+
+        let stream_chunks = generate_browse_layout(stream_chunks_with_margin[1]);
+        let streams_focused = focused_panel == BrowsePanel::Streams;
+
+        hits.add(stream_chunks[0], Target::Panel(BrowsePanel::Streams));
+
+        frame.render_widget(
+            generate_title_with_info(
+                category.name.as_str(),
+                &loading_info(streams, timer),
+                theme.elevation(Elevation::Level2).as_tui_colour(),
+                theme.primary.as_tui_colour(),
+                theme.text_dimmed.as_tui_colour(),
+                streams_focused,
+            ),
+            stream_chunks[0],
+        );
+
+        if streams.items.is_empty() {
+            frame.render_widget(
+                generate_message_widget(
+                    theme,
+                    message(streams, "Nobody is live in this category", timer),
+                ),
+                stream_chunks[1],
+            );
+        } else {
+            let mut list_state: ListState = ListState::default();
+            list_state.select(Some(streams.highlight));
+
+            frame.render_stateful_widget(
+                generate_list_widget(
+                    theme,
+                    generate_channel_items(theme, streams.items, columns),
+                    streams_focused,
+                ),
+                stream_chunks[1],
+                &mut list_state,
+            );
+
+            hits.list(
+                BrowsePanel::Streams,
+                stream_chunks[1],
+                streams.items.len(),
+                Some(streams.highlight),
+            );
+        }
+    }
+
+    frame.render_widget(generate_keys_widget(theme, keybinds), app_layout[3]);
+
+    hits
+}
+
+// Further pages load under what is already listed, so that is only flagged in the title
+fn loading_info<T>(list: &BrowseList<'_, T>, timer: u64) -> String {
+    if list.loading && !list.items.is_empty() {
+        format!("loading more{}", animate_ellipsis(timer))
+    } else {
+        String::new()
+    }
+}
+
+fn message<T>(list: &BrowseList<'_, T>, empty: &str, timer: u64) -> String {
+    match (list.loading, list.error) {
+        (true, _) => format!("Loading{}", animate_ellipsis(timer)),
+        (false, Some(e)) => e.to_owned(),
+        (false, None) => empty.to_owned(),
+    }
+}
+
+fn generate_content_area_layout(area: Rect) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .horizontal_margin(HORIZONTAL_MARGIN)
+        .vertical_margin(VERTICAL_MARGIN)
+        .constraints([Constraint::Percentage(30), Constraint::Min(1)].as_ref())
+        .split(area)
+}
+
+fn generate_browse_layout(area: Rect) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(area)
+}
+
+fn generate_message_widget<'a>(theme: &Theme, message: String) -> Paragraph<'a> {
+    Paragraph::new(message)
+        .style(
+            Style::default()
+                .fg(theme.text_dimmed.as_tui_colour())
+                .bg(theme.elevation(Elevation::Level2).as_tui_colour()),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+}
+
+fn generate_list_widget<'a>(theme: &Theme, items: Vec<ListItem<'a>>, focused: bool) -> List<'a> {
+    let mut block_style = Style::default().fg(theme.text.as_tui_colour());
+
+    if !focused {
+        block_style = block_style.add_modifier(Modifier::DIM);
+    }
+
+    List::new(items)
+        .block(Block::default().style(block_style))
+        .style(Style::default().bg(theme.elevation(Elevation::Level2).as_tui_colour()))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol(" >")
+}
@@ -1,15 +1,23 @@
-use crate::theme::{Elevation, Theme};
+use crate::{
+    column::Column,
+    theme::{Elevation, Theme},
+};
+use std::{cmp::min, time::Duration};
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph, Tabs, Wrap},
+    widgets::{Block, Borders, ListItem, Paragraph, Tabs, Wrap},
 };
+use twitch::{channel::Channel, status::Status};
 
 pub const HORIZONTAL_MARGIN: u16 = 2;
 pub const VERTICAL_MARGIN: u16 = 1;
 
-pub const TAB_TITLES: [&str; 2] = ["Home", "Lists"];
+pub const TAB_TITLES: [&str; 4] = ["Home", "Lists", "Follows", "Browse"];
+
+const NAME_MAX_WIDTH: usize = 25;
+const COLUMN_SPACING: usize = 2;
 
 pub fn animate_ellipsis(timer: u64) -> String {
     (0..((timer / 2) % 4)).map(|_| ".").collect::<String>()
@@ -65,6 +73,17 @@ pub fn generate_title<'a>(
     bg_colour: Color,
     text_colour: Color,
     focused: bool,
+) -> Paragraph<'a> {
+    generate_title_with_info(title, "", bg_colour, text_colour, text_colour, focused)
+}
+
+pub fn generate_title_with_info<'a>(
+    title: &str,
+    info: &str,
+    bg_colour: Color,
+    text_colour: Color,
+    info_colour: Color,
+    focused: bool,
 ) -> Paragraph<'a> {
     let mut block_style = Style::default().bg(bg_colour);
 
@@ -82,6 +101,7 @@ pub fn generate_title<'a>(
                     .fg(text_colour)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::styled(format!("  {info}"), Style::default().fg(info_colour)),
         ]),
     ])
     .block(Block::default().style(block_style))
@@ -151,3 +171,105 @@ pub fn generate_keys_widget<'a>(theme: &Theme, keybinds: &[String]) -> Paragraph
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
 }
+
+#[must_use]
+pub fn last_updated(since: Option<Duration>) -> String {
+    match since.map(|duration| duration.as_secs()) {
+        None => String::new(),
+        Some(0..=4) => String::from("updated just now"),
+        Some(seconds @ 5..=59) => format!("updated {seconds}s ago"),
+        Some(seconds @ 60..=3599) => format!("updated {}m ago", seconds / 60),
+        Some(seconds) => format!("updated {}h ago", seconds / 3600),
+    }
+}
+
+/// One row per channel, with the columns lined up across rows.
+pub fn generate_channel_items<'a>(
+    theme: &Theme,
+    channels: &[Channel],
+    columns: &[Column],
+) -> Vec<ListItem<'a>> {
+    let online_style = Style::default().fg(theme.secondary.as_tui_colour());
+    let offline_style = Style::default().fg(theme.text_dimmed.as_tui_colour());
+    let unknown_status_style = Style::default().fg(theme.text.as_tui_colour());
+    let throttled_status_style = Style::default().fg(theme.primary.as_tui_colour());
+    let awaiting_status_style = Style::default().fg(theme.text.as_tui_colour());
+    let text_style = Style::default().fg(theme.text.as_tui_colour());
+    let info_style = Style::default().fg(theme.text_dimmed.as_tui_colour());
+
+    let name_width = min(
+        channels
+            .iter()
+            .map(|channel| channel.friendly_name.chars().count())
+            .max()
+            .unwrap_or_default(),
+        NAME_MAX_WIDTH,
+    );
+
+    let column_widths: Vec<usize> = columns
+        .iter()
+        .map(|column| {
+            min(
+                channels
+                    .iter()
+                    .map(|channel| column.text(channel).chars().count())
+                    .max()
+                    .unwrap_or_default(),
+                column.max_width(),
+            )
+        })
+        .collect();
+
+    channels
+        .iter()
+        .map(|channel| {
+            let status_style = match channel.status {
+                Status::Awaiting => awaiting_status_style,
+                Status::Online => online_style,
+                Status::Offline => offline_style,
+                Status::Throttled => throttled_status_style,
+                Status::Unknown => unknown_status_style,
+            };
+
+            let mut spans = vec![Span::styled(
+                format!(" {}", fit(&channel.friendly_name, name_width)),
+                text_style,
+            )];
+
+            for (column, width) in columns.iter().zip(&column_widths) {
+                // Don't leave gaps for columns nobody has anything in
+                if *width == 0 {
+                    continue;
+                }
+
+                spans.push(Span::styled(
+                    format!(
+                        "{:spacing$}{}",
+                        "",
+                        fit(&column.text(channel), *width),
+                        spacing = COLUMN_SPACING
+                    ),
+                    if *column == Column::Status {
+                        status_style
+                    } else {
+                        info_style
+                    },
+                ));
+            }
+
+            ListItem::new(Spans::from(spans))
+        })
+        .collect()
+}
+
+// Pads or cuts `text` to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return format!("{text:width$}");
+    }
+
+    let mut fitted: String = text.chars().take(width.saturating_sub(1)).collect();
+    fitted.push('…');
+
+    fitted
+}
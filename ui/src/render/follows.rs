@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+use twitch::channel::Channel;
+
+use crate::{
+    column::Column,
+    hit::Hits,
+    theme::{Elevation, Theme},
+};
+
+use super::common::{
+    animate_ellipsis, generate_app_layout, generate_background_widget, generate_channel_items,
+    generate_keys_widget, generate_tabs_widget, generate_title_with_info, last_updated,
+    HORIZONTAL_MARGIN, VERTICAL_MARGIN,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn follows<B: Backend>(
+    theme: &Theme,
+    frame: &mut Frame<'_, B>,
+    highlight: usize,
+    channels: &[Channel],
+    columns: &[Column],
+    loading: bool,
+    error: Option<&str>,
+    keybinds: &[String],
+    since_update: Option<Duration>,
+    timer: u64,
+) -> Hits<()> {
+    let mut hits = Hits::default();
+
+    let area = frame.size();
+
+    frame.render_widget(
+        generate_background_widget(theme.background.as_tui_colour()),
+        area,
+    );
+
+    let app_layout = generate_app_layout(area);
+
+    frame.render_widget(
+        generate_background_widget(theme.elevation(Elevation::Level1).as_tui_colour()),
+        app_layout[2],
+    );
+
+    frame.render_widget(generate_tabs_widget(2, theme), app_layout[0]);
+    hits.tabs(app_layout[0]);
+
+    let follows_chunks = generate_follows_layout(app_layout[2]);
+
+    frame.render_widget(
+        generate_title_with_info(
+            "Followed Channels",
+            &last_updated(since_update),
+            theme.elevation(Elevation::Level2).as_tui_colour(),
+            theme.primary.as_tui_colour(),
+            theme.text_dimmed.as_tui_colour(),
+            true,
+        ),
+        follows_chunks[0],
+    );
+
+    // Whatever was fetched last stays up while refreshing, messages only replace an empty list
+    if channels.is_empty() {
+        frame.render_widget(
+            Paragraph::new(message(loading, error, timer))
+                .style(
+                    Style::default()
+                        .fg(theme.text_dimmed.as_tui_colour())
+                        .bg(theme.elevation(Elevation::Level2).as_tui_colour()),
+                )
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            follows_chunks[1],
+        );
+    } else {
+        let mut list_state: ListState = ListState::default();
+        list_state.select(Some(highlight));
+
+        frame.render_stateful_widget(
+            generate_follows_widget(theme, channels, columns),
+            follows_chunks[1],
+            &mut list_state,
+        );
+
+        hits.list((), follows_chunks[1], channels.len(), Some(highlight));
+    }
+
+    frame.render_widget(generate_keys_widget(theme, keybinds), app_layout[3]);
+
+    hits
+}
+
+fn message(loading: bool, error: Option<&str>, timer: u64) -> String {
+    match (loading, error) {
+        (true, _) => format!("Loading{}", animate_ellipsis(timer)),
+        (false, Some(e)) => format!("Could not load followed channels: {e}"),
+        (false, None) => String::from("Not following anyone yet"),
+    }
+}
+
+fn generate_follows_layout(area: Rect) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .horizontal_margin(HORIZONTAL_MARGIN)
+        .vertical_margin(VERTICAL_MARGIN)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(area)
+}
+
+fn generate_follows_widget<'a>(
+    theme: &Theme,
+    channels: &[Channel],
+    columns: &[Column],
+) -> List<'a> {
+    let items = generate_channel_items(theme, channels, columns);
+
+    // TODO allow theme to have highlight style? Or make constant
+
+    List::new(items)
+        .block(Block::default().style(Style::default().fg(theme.text.as_tui_colour())))
+        .style(Style::default().bg(theme.elevation(Elevation::Level2).as_tui_colour()))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol(" >")
+}
@@ -1,21 +1,23 @@
 use crate::{
+    column::Column,
+    hit::{Hits, Target},
     panel::Home,
     render::common::{
-        generate_app_layout, generate_background_widget, generate_input_box, generate_keys_widget,
-        generate_tabs_widget, generate_title, HORIZONTAL_MARGIN, VERTICAL_MARGIN,
+        generate_app_layout, generate_background_widget, generate_channel_items,
+        generate_input_box, generate_keys_widget, generate_tabs_widget, generate_title_with_info,
+        last_updated, HORIZONTAL_MARGIN, VERTICAL_MARGIN,
     },
     theme::{Elevation, Theme},
 };
-use std::cmp::{max, min};
+use std::time::Duration;
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     terminal::Frame,
-    text::{Span, Spans},
-    widgets::{Block, List, ListItem, ListState},
+    widgets::{Block, List, ListState, Paragraph, Wrap},
 };
-use twitch::channel::{status::Status, Channel};
+use twitch::channel::Channel;
 
 #[allow(clippy::trivially_copy_pass_by_ref, clippy::too_many_arguments)]
 pub fn home<B: Backend>(
@@ -23,11 +25,18 @@ pub fn home<B: Backend>(
     frame: &mut Frame<'_, B>,
     channel_highlight: &usize,
     channels: &[Channel],
+    columns: &[Column],
     typing: bool,
     search_input: &[char],
+    search_results: &[Channel],
+    search_highlight: Option<usize>,
+    search_message: Option<&str>,
     focused_panel: &Home,
     keybinds: &[String],
-) {
+    since_update: Option<Duration>,
+) -> Hits<Home> {
+    let mut hits = Hits::default();
+
     let area = frame.size();
 
     frame.render_widget(
@@ -45,6 +54,7 @@ pub fn home<B: Backend>(
     let content_area = generate_content_area_layout(app_layout[2]);
 
     frame.render_widget(generate_tabs_widget(0, theme), app_layout[0]);
+    hits.tabs(app_layout[0]);
 
     let list_chunks = generate_favourites_layout(content_area[0]);
 
@@ -52,10 +62,12 @@ pub fn home<B: Backend>(
     let search_focused = *focused_panel == Home::Search;
 
     frame.render_widget(
-        generate_title(
+        generate_title_with_info(
             "Favourites",
+            &last_updated(since_update),
             theme.elevation(Elevation::Level2).as_tui_colour(),
             theme.primary.as_tui_colour(),
+            theme.text_dimmed.as_tui_colour(),
             favourites_focused,
         ),
         list_chunks[0],
@@ -65,11 +77,19 @@ pub fn home<B: Backend>(
     list_state.select(Some(*channel_highlight));
 
     frame.render_stateful_widget(
-        generate_favourites_widget(theme, channels, content_area[0].width, favourites_focused),
+        generate_channel_list_widget(theme, channels, columns, favourites_focused),
         list_chunks[1],
         &mut list_state,
     );
 
+    hits.add(list_chunks[0], Target::Panel(Home::Favourites));
+    hits.list(
+        Home::Favourites,
+        list_chunks[1],
+        channels.len(),
+        Some(*channel_highlight),
+    );
+
     let search_chunks_with_margin = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Length(HORIZONTAL_MARGIN), Constraint::Min(1)].as_ref())
@@ -82,6 +102,8 @@ pub fn home<B: Backend>(
 
     let search_chunks = generate_search_layout(search_chunks_with_margin[1]);
 
+    hits.add(search_chunks_with_margin[1], Target::Panel(Home::Search));
+
     frame.render_widget(
         generate_input_box(
             theme,
@@ -94,7 +116,44 @@ pub fn home<B: Backend>(
         search_chunks[1],
     );
 
+    if let Some(message) = search_message {
+        frame.render_widget(
+            Paragraph::new(message.to_owned())
+                .style(
+                    Style::default()
+                        .fg(theme.text_dimmed.as_tui_colour())
+                        .bg(theme.elevation(Elevation::Level2).as_tui_colour()),
+                )
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            search_chunks[3],
+        );
+    } else {
+        let mut results_state: ListState = ListState::default();
+        results_state.select(search_highlight);
+
+        frame.render_stateful_widget(
+            generate_channel_list_widget(
+                theme,
+                search_results,
+                &[Column::Status, Column::Game, Column::Title],
+                search_focused,
+            ),
+            search_chunks[3],
+            &mut results_state,
+        );
+
+        hits.list(
+            Home::Search,
+            search_chunks[3],
+            search_results.len(),
+            search_highlight,
+        );
+    }
+
     frame.render_widget(generate_keys_widget(theme, keybinds), app_layout[3]);
+
+    hits
 }
 
 fn generate_content_area_layout(area: Rect) -> Vec<Rect> {
@@ -113,46 +172,13 @@ fn generate_favourites_layout(area: Rect) -> Vec<Rect> {
         .split(area)
 }
 
-fn generate_favourites_widget<'a>(
+fn generate_channel_list_widget<'a>(
     theme: &Theme,
-    channels: &'a [Channel],
-    width: u16,
+    channels: &[Channel],
+    columns: &[Column],
     focused: bool,
 ) -> List<'a> {
-    let online_style = Style::default().fg(theme.secondary.as_tui_colour());
-    let offline_style = Style::default().fg(theme.text_dimmed.as_tui_colour());
-    let unknown_status_style = Style::default().fg(theme.text.as_tui_colour());
-    let awaiting_status_style = Style::default().fg(theme.text.as_tui_colour());
-    let text_style = Style::default().fg(theme.text.as_tui_colour());
-
-    let items: Vec<ListItem<'a>> = channels
-        .iter()
-        .map(|entry| {
-            let status_style = match entry.status {
-                Status::Awaiting => awaiting_status_style,
-                Status::Online => online_style,
-                Status::Offline => offline_style,
-                Status::Unknown => unknown_status_style,
-            };
-
-            ListItem::new(Spans::from(vec![
-                Span::styled(
-                    format!(
-                        " {:text_width$}",
-                        entry.friendly_name.as_str(),
-                        text_width = min(
-                            max(width as usize, entry.status.message().len() - 5)
-                                - entry.status.message().len()
-                                - 5,
-                            25,
-                        ),
-                    ),
-                    text_style,
-                ),
-                Span::styled(entry.status.message(), status_style),
-            ]))
-        })
-        .collect();
+    let items = generate_channel_items(theme, channels, columns);
 
     let mut block_style = Style::default().fg(theme.text.as_tui_colour());
 
@@ -186,9 +212,10 @@ fn generate_search_layout(area: Rect) -> Vec<Rect> {
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Percentage(42), // Top Margin
-                Constraint::Length(3),      // Search Bar
-                Constraint::Min(1),         // Bottom Margin
+                Constraint::Length(1), // Top Margin
+                Constraint::Length(3), // Search Bar
+                Constraint::Length(1), // Gap
+                Constraint::Min(1),    // Results
             ]
             .as_ref(),
         )
@@ -1,4 +1,7 @@
-use std::cmp::{max, min};
+use std::{
+    cmp::{max, min},
+    time::Duration,
+};
 
 use tui::{
     backend::Backend,
@@ -8,19 +11,19 @@ use tui::{
     widgets::{Block, List, ListItem, ListState},
     Frame,
 };
-use twitch::{
-    channel::{Channel, List as ChannelList},
-    status::Status,
-};
+use twitch::channel::{Channel, List as ChannelList};
 
 use crate::{
+    column::Column,
+    hit::{Hits, Target},
     panel::Lists as ListsPanel,
     theme::{Elevation, Theme},
 };
 
 use super::common::{
-    generate_app_layout, generate_background_widget, generate_keys_widget, generate_tabs_widget,
-    generate_title, HORIZONTAL_MARGIN, VERTICAL_MARGIN,
+    generate_app_layout, generate_background_widget, generate_channel_items, generate_keys_widget,
+    generate_tabs_widget, generate_title, generate_title_with_info, last_updated,
+    HORIZONTAL_MARGIN, VERTICAL_MARGIN,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -29,11 +32,15 @@ pub fn lists<B: Backend>(
     frame: &mut Frame<'_, B>,
     highlight: usize,
     lists: &[ChannelList],
+    columns: &[Column],
     keybinds: &[String],
     focused_panel: ListsPanel,
     selected_list: Option<usize>,
     channel_highlight: usize,
-) {
+    since_update: Option<Duration>,
+) -> Hits<ListsPanel> {
+    let mut hits = Hits::default();
+
     let area = frame.size();
 
     frame.render_widget(
@@ -51,6 +58,7 @@ pub fn lists<B: Backend>(
     let content_area = generate_content_area_layout(app_layout[2]);
 
     frame.render_widget(generate_tabs_widget(1, theme), app_layout[0]);
+    hits.tabs(app_layout[0]);
 
     let list_chunks = generate_lists_layout(content_area[0]);
 
@@ -78,6 +86,14 @@ pub fn lists<B: Backend>(
         &mut list_state,
     );
 
+    hits.add(list_chunks[0], Target::Panel(ListsPanel::Lists));
+    hits.list(
+        ListsPanel::Lists,
+        list_chunks[1],
+        lists.len(),
+        Some(highlight),
+    );
+
     if let Some(selected_list_index) = selected_list {
         if let Some(open_list) = lists.get(selected_list_index) {
             let open_list_chunks_with_margin = Layout::default()
@@ -88,10 +104,12 @@ pub fn lists<B: Backend>(
             let open_list_chunks = generate_lists_layout(open_list_chunks_with_margin[1]);
 
             frame.render_widget(
-                generate_title(
+                generate_title_with_info(
                     open_list.name.as_str(),
+                    &last_updated(since_update),
                     theme.elevation(Elevation::Level2).as_tui_colour(),
                     theme.primary.as_tui_colour(),
+                    theme.text_dimmed.as_tui_colour(),
                     focused_panel == ListsPanel::ListContent,
                 ),
                 open_list_chunks[0],
@@ -104,16 +122,26 @@ pub fn lists<B: Backend>(
                 generate_channel_list_widget(
                     theme,
                     &open_list.channels,
-                    content_area[1].width,
+                    columns,
                     focused_panel == ListsPanel::ListContent,
                 ),
                 open_list_chunks[1],
                 &mut open_list_state,
             );
+
+            hits.add(open_list_chunks[0], Target::Panel(ListsPanel::ListContent));
+            hits.list(
+                ListsPanel::ListContent,
+                open_list_chunks[1],
+                open_list.channels.len(),
+                Some(channel_highlight),
+            );
         }
     }
 
     frame.render_widget(generate_keys_widget(theme, keybinds), app_layout[3]);
+
+    hits
 }
 
 fn generate_content_area_layout(area: Rect) -> Vec<Rect> {
@@ -185,44 +213,11 @@ fn generate_lists_widget<'a>(
 
 fn generate_channel_list_widget<'a>(
     theme: &Theme,
-    channels: &'a [Channel],
-    width: u16,
+    channels: &[Channel],
+    columns: &[Column],
     focused: bool,
 ) -> List<'a> {
-    let online_style = Style::default().fg(theme.secondary.as_tui_colour());
-    let offline_style = Style::default().fg(theme.text_dimmed.as_tui_colour());
-    let unknown_status_style = Style::default().fg(theme.text.as_tui_colour());
-    let awaiting_status_style = Style::default().fg(theme.text.as_tui_colour());
-    let text_style = Style::default().fg(theme.text.as_tui_colour());
-
-    let items: Vec<ListItem<'a>> = channels
-        .iter()
-        .map(|entry| {
-            let status_style = match entry.status {
-                Status::Awaiting => awaiting_status_style,
-                Status::Online => online_style,
-                Status::Offline => offline_style,
-                Status::Unknown => unknown_status_style,
-            };
-
-            ListItem::new(Spans::from(vec![
-                Span::styled(
-                    format!(
-                        " {:text_width$}",
-                        entry.friendly_name.as_str(),
-                        text_width = min(
-                            max(width as usize, entry.status.message().len() - 5)
-                                - entry.status.message().len()
-                                - 5,
-                            25,
-                        ),
-                    ),
-                    text_style,
-                ),
-                Span::styled(entry.status.message(), status_style),
-            ]))
-        })
-        .collect();
+    let items = generate_channel_items(theme, channels, columns);
 
     let mut block_style = Style::default().fg(theme.text.as_tui_colour());
 
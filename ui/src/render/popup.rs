@@ -1,4 +1,5 @@
 use crate::{
+    hit::{Hits, Target},
     render::common::{generate_background_widget, generate_input_box, generate_keys_widget},
     theme::{Elevation, Theme},
 };
@@ -24,7 +25,9 @@ pub fn choice<B: Backend>(
     message: &str,
     selected: usize,
     options: &[String],
-) {
+) -> Hits<()> {
+    let mut hits = Hits::default();
+
     let area = self::popup(
         theme,
         frame,
@@ -42,6 +45,12 @@ pub fn choice<B: Backend>(
         area,
         &mut list_state,
     );
+
+    // Scrolling anywhere over the options moves the selection
+    hits.add(area, Target::Panel(()));
+    hits.choices(area, options.len(), selected);
+
+    hits
 }
 
 pub fn input<B: Backend>(
@@ -139,7 +148,7 @@ fn generate_choice_popup_layout(area: Rect) -> Vec<Rect> {
     Layout::default()
         .direction(Direction::Vertical)
         .constraints(
-            vec![
+            [
                 Constraint::Percentage(67), // message
                 Constraint::Percentage(33), // options
             ]
@@ -169,7 +178,11 @@ fn generate_choice_popup_list<'a>(theme: &Theme, options: &[String]) -> List<'a>
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref, clippy::cast_possible_truncation)]
-fn generate_timed_popup_progress_bar<'a>(theme: &Theme, duration: u64, timer: u64) -> Gauge<'a> {
+pub(crate) fn generate_timed_popup_progress_bar<'a>(
+    theme: &Theme,
+    duration: u64,
+    timer: u64,
+) -> Gauge<'a> {
     Gauge::default()
         .block(Block::default())
         .gauge_style(Style::default().fg(theme.text_dimmed.as_tui_colour()))
@@ -181,7 +194,7 @@ fn generate_input_popup_layout(area: Rect) -> Vec<Rect> {
     Layout::default()
         .direction(Direction::Vertical)
         .constraints(
-            vec![
+            [
                 Constraint::Percentage(50), // message
                 Constraint::Percentage(50), // input box
             ]
@@ -194,7 +207,7 @@ fn generate_timed_info_popup_layout(area: Rect) -> Vec<Rect> {
     Layout::default()
         .direction(Direction::Vertical)
         .constraints(
-            vec![
+            [
                 Constraint::Percentage(95), // message
                 Constraint::Percentage(5),  // progress bar
             ]
@@ -0,0 +1,88 @@
+use crate::{
+    render::popup::generate_timed_popup_progress_bar,
+    theme::{Elevation, Theme},
+    toast::{Severity, Toast},
+};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    terminal::Frame,
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+const TOAST_WIDTH: u16 = 48;
+// Borders, two lines of message and the progress bar
+const TOAST_HEIGHT: u16 = 5;
+
+/// Stacks `toasts` in the top right corner, the newest on top, as many as fit.
+pub fn toasts<B: Backend>(theme: &Theme, frame: &mut Frame<'_, B>, toasts: &[Toast], now: u64) {
+    let area = frame.size();
+
+    let width = TOAST_WIDTH.min(area.width);
+    let x = area.right().saturating_sub(width);
+
+    for (row, toast) in toasts.iter().rev().enumerate() {
+        let y = u16::try_from(row)
+            .unwrap_or(u16::MAX)
+            .saturating_mul(TOAST_HEIGHT);
+
+        if y.saturating_add(TOAST_HEIGHT) > area.bottom() {
+            break;
+        }
+
+        let toast_area = Rect {
+            x,
+            y: area.y + y,
+            width,
+            height: TOAST_HEIGHT,
+        };
+
+        render_toast(theme, frame, toast, now, toast_area);
+    }
+}
+
+fn render_toast<B: Backend>(
+    theme: &Theme,
+    frame: &mut Frame<'_, B>,
+    toast: &Toast,
+    now: u64,
+    area: Rect,
+) {
+    let colour = match toast.severity {
+        Severity::Info => theme.primary.as_tui_colour(),
+        Severity::Warning | Severity::Error => theme.secondary.as_tui_colour(),
+    };
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!("{}: {}", toast.severity, toast.title),
+            Style::default().fg(colour).add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colour))
+        .style(Style::default().bg(theme.elevation(Elevation::Level8).as_tui_colour()));
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(block.inner(area));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    frame.render_widget(
+        Paragraph::new(toast.message.as_str())
+            .style(Style::default().fg(theme.text.as_tui_colour()))
+            .wrap(Wrap { trim: true }),
+        sections[0],
+    );
+
+    let duration = toast.severity.duration();
+
+    frame.render_widget(
+        generate_timed_popup_progress_bar(theme, duration, (now - toast.shown).min(duration)),
+        sections[1],
+    );
+}
@@ -1,10 +1,36 @@
+use anyhow::{anyhow, Error, Result};
 use num::clamp;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fs::read_dir, path::Path};
+use tracing::warn;
 use tui::style::{Color, Modifier};
+use twitch::{paths, storage};
 
 // TODO write unit(+integration?) tests
-// TODO this is a strong argument for a different storage format (e.g. TOML/YAML)
 
-#[derive(Clone)]
+const THEME_FILE: &str = "theme.toml";
+const THEMES_DIR: &str = "themes";
+const THEME_FILE_EXTENSION: &str = ".toml";
+
+const DEFAULT_THEME: &str = "Default";
+const LIGHT_THEME: &str = "Light";
+
+// How cursor modifiers are written in theme files
+const MODIFIERS: [(&str, Modifier); 9] = [
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("slow_blink", Modifier::SLOW_BLINK),
+    ("rapid_blink", Modifier::RAPID_BLINK),
+    ("reversed", Modifier::REVERSED),
+    ("hidden", Modifier::HIDDEN),
+    ("crossed_out", Modifier::CROSSED_OUT),
+];
+
+// Written as "#rrggbb" in theme files
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct CustomColour {
     pub r: u8,
     pub g: u8,
@@ -25,8 +51,6 @@ pub enum Elevation {
     Level24 = 9,
 }
 
-// TODO implement theme loading/saving
-
 impl CustomColour {
     #[must_use]
     #[allow(
@@ -152,6 +176,31 @@ impl CustomColour {
     }
 }
 
+impl TryFrom<String> for CustomColour {
+    type Error = Error;
+
+    fn try_from(hex: String) -> Result<Self> {
+        let digits = hex.trim_start_matches('#');
+
+        let channel = |range| {
+            digits
+                .get(range)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+
+        match (digits.len(), channel(0..2), channel(2..4), channel(4..6)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Self { r, g, b }),
+            _ => Err(anyhow!("{hex} is not a colour, expected #rrggbb")),
+        }
+    }
+}
+
+impl From<CustomColour> for String {
+    fn from(colour: CustomColour) -> Self {
+        format!("#{:02x}{:02x}{:02x}", colour.r, colour.g, colour.b)
+    }
+}
+
 const BG_GREY: CustomColour = CustomColour {
     r: 18,
     g: 18,
@@ -168,9 +217,14 @@ const BLACK: CustomColour = CustomColour { r: 0, g: 0, b: 0 };
 
 const ELEVATION: [f32; 10] = [0.0, 0.05, 0.07, 0.08, 0.09, 0.11, 0.12, 0.14, 0.15, 0.16];
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Cursor {
     pub cursor: String,
+    #[serde(
+        serialize_with = "serialize_modifier",
+        deserialize_with = "deserialize_modifier"
+    )]
     pub modifier: Modifier,
 }
 
@@ -183,33 +237,168 @@ impl Default for Cursor {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Theme {
+    pub name: String,
     pub background: CustomColour,
     pub primary: CustomColour,
     pub secondary: CustomColour,
     pub text: CustomColour,
     pub text_dimmed: CustomColour,
+    /// What raised surfaces, like popups, are blended towards.
+    pub elevation_tint: CustomColour,
     pub cursor: Cursor,
 }
 
 impl Default for Theme {
     fn default() -> Self {
         Self {
+            name: String::from(DEFAULT_THEME),
             background: BG_GREY,
             primary: CustomColour::from(Color::Yellow),
             secondary: CustomColour::from(Color::LightRed).blend(&BLACK, 0.1),
             text: WHITE,
             text_dimmed: WHITE.blend(&BLACK, 0.5),
+            elevation_tint: WHITE,
             cursor: Cursor::default(),
         }
     }
 }
 
 impl Theme {
+    #[allow(clippy::missing_errors_doc)]
+    pub fn load_from_file(file: &Path) -> Result<Self> {
+        storage::load(file)
+    }
+
+    /// The theme in `theme.toml`, or the default one if there isn't one.
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from_file(&paths::config_file(THEME_FILE)).unwrap_or_default()
+    }
+
+    /// Makes this the theme in `theme.toml`, so it is used again on the next start.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn save(&self) -> Result<()> {
+        storage::save(&paths::config_file(THEME_FILE), self)
+    }
+
+    /// The theme offered as `name`, looking in the `themes` folder before the built in ones.
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        let file = paths::config_file(THEMES_DIR).join(format!("{name}{THEME_FILE_EXTENSION}"));
+
+        if file.is_file() {
+            return match Self::load_from_file(&file) {
+                Ok(mut theme) => {
+                    theme.name = name.to_owned();
+                    Some(theme)
+                }
+                Err(e) => {
+                    warn!("Skipping theme {}: {e:#}", file.display());
+                    None
+                }
+            };
+        }
+
+        [Self::default(), Self::light()]
+            .into_iter()
+            .find(|theme| theme.name == name)
+    }
+
+    /// The themes that come with the launcher, then those in the `themes` folder, which are named
+    /// after their file. A file can replace a built in theme by using its name.
+    #[must_use]
+    pub fn available() -> Vec<Self> {
+        let mut themes = vec![Self::default(), Self::light()];
+
+        let dir = paths::config_file(THEMES_DIR);
+
+        let mut files: Vec<_> = match read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| {
+                    file.to_string_lossy().ends_with(THEME_FILE_EXTENSION) && file.is_file()
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        files.sort();
+
+        for file in files {
+            let name = match file.file_stem() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => continue,
+            };
+
+            match Self::load_from_file(&file) {
+                Ok(mut theme) => {
+                    theme.name = name;
+                    themes.retain(|existing| existing.name != theme.name);
+                    themes.push(theme);
+                }
+                Err(e) => warn!("Skipping theme {}: {e:#}", file.display()),
+            }
+        }
+
+        themes
+    }
+
+    fn light() -> Self {
+        Self {
+            name: String::from(LIGHT_THEME),
+            background: CustomColour {
+                r: 245,
+                g: 245,
+                b: 240,
+            },
+            primary: CustomColour::from(Color::Blue),
+            secondary: CustomColour::from(Color::Red),
+            text: CustomColour {
+                r: 28,
+                g: 28,
+                b: 28,
+            },
+            text_dimmed: CustomColour::from(Color::DarkGray),
+            elevation_tint: BLACK,
+            cursor: Cursor::default(),
+        }
+    }
+
     #[allow(clippy::needless_arbitrary_self_type)]
     #[must_use]
     pub fn elevation(self: &Self, level: Elevation) -> CustomColour {
-        self.background.blend(&WHITE, ELEVATION[level as usize])
+        self.background
+            .blend(&self.elevation_tint, ELEVATION[level as usize])
     }
 }
+
+fn serialize_modifier<S: Serializer>(
+    modifier: &Modifier,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let names: Vec<&str> = MODIFIERS
+        .iter()
+        .filter(|(_, flag)| modifier.contains(*flag))
+        .map(|(name, _)| *name)
+        .collect();
+
+    names.serialize(serializer)
+}
+
+fn deserialize_modifier<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Modifier, D::Error> {
+    Vec::<String>::deserialize(deserializer)?.iter().try_fold(
+        Modifier::empty(),
+        |modifier, name| {
+            MODIFIERS
+                .iter()
+                .find(|(known, _)| known == name)
+                .map(|(_, flag)| modifier | *flag)
+                .ok_or_else(|| serde::de::Error::custom(format!("{name} is not a cursor modifier")))
+        },
+    )
+}
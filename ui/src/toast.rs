@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Ticks a toast stays up for, errors stay longest so they can be read.
+    #[must_use]
+    pub const fn duration(self) -> u64 {
+        match self {
+            Self::Info => 16,
+            Self::Warning => 24,
+            Self::Error => 40,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match *self {
+            Self::Info => write!(f, "Info"),
+            Self::Warning => write!(f, "Warning"),
+            Self::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// A notification shown over whatever is on screen, without taking the keys.
+#[derive(Clone)]
+pub struct Toast {
+    pub severity: Severity,
+    pub title: String,
+    pub message: String,
+    /// The tick it was (last) shown at.
+    pub shown: u64,
+}
+
+impl Toast {
+    #[must_use]
+    pub const fn expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.shown) >= self.severity.duration()
+    }
+}